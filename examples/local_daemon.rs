@@ -2,19 +2,21 @@
 //! You can also start a juno container by running `just juno-local`.
 //!
 //! Ensure the local juno is running before executing this script.
+//! The `ARCHID_REGISTRY` environment variable must point to an ArchID registry deployed on it.
 //! Also make sure port 9090 is exposed on the local juno container. This port is used to communicate with the chain.
 //!
 //! # Run
 //!
 //! `cargo run --example local_daemon`
 
-use abstract_app::objects::namespace::Namespace;
+use abstract_app::objects::{namespace::Namespace, AssetEntry};
 use abstract_client::{AbstractClient, Publisher};
 use app::{
     contract::{APP_ID, APP_VERSION},
-    msg::AppInstantiateMsg,
+    msg::{AppInstantiateMsg, RegistryAddress},
     AppInterface,
 };
+use cosmwasm_std::Uint128;
 use cw_orch::{anyhow, prelude::*, tokio::runtime::Runtime};
 use semver::Version;
 use speculoos::assert_that;
@@ -56,17 +58,25 @@ fn main() -> anyhow::Result<()> {
 
     let account = abstract_client.account_builder().build()?;
     // Installs the app on the Account
-    let app = account.install_app::<AppInterface<_>>(&AppInstantiateMsg { count: 0 }, &[])?;
+    let registry = std::env::var("ARCHID_REGISTRY")?;
+    let app = account.install_app::<AppInterface<_>>(
+        &AppInstantiateMsg {
+            native_asset: AssetEntry::new("juno>juno"),
+            task_creation_amount: Uint128::new(1_000_000),
+            refill_threshold: Uint128::new(100_000),
+            registry: RegistryAddress::Addr(registry.clone()),
+            registry_asset: AssetEntry::new("juno>juno"),
+            max_registration_years: None,
+        },
+        &[],
+    )?;
 
     // Import app's endpoint function traits for easy interactions.
-    use app::{AppExecuteMsgFns, AppQueryMsgFns};
-    assert_that!(app.count()?.count).is_equal_to(0);
+    use app::AppQueryMsgFns;
+    assert_that!(app.config()?.registry_addr.to_string()).is_equal_to(registry);
 
-    // Execute the App
-    app.increment()?;
-
-    // Query the App again
-    assert_that!(app.count()?.count).is_equal_to(1);
+    // No domains are registered yet
+    assert_that!(app.domains(None, None)?.domains).is_empty();
 
     // Note: the App is installed on a sub-account of the main account!
     assert_ne!(account.id()?, app.account().id()?);
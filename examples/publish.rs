@@ -9,22 +9,45 @@
 //! ```
 use abstract_app::{abstract_core::ans_host::QueryMsgFns, objects::{namespace::Namespace, AccountId, AssetEntry}};
 use abstract_client::{AbstractClient, Publisher};
-use app::{contract::{App, APP_ID}, msg::{AppExecuteMsg, AppInstantiateMsg, AppMigrateMsg, ExecuteMsg}, state::TaskId, AppExecuteMsgFns, AppInterface, AppQueryMsgFns};
+use app::{contract::{App, APP_ID}, msg::{AppExecuteMsg, AppInstantiateMsg, AppMigrateMsg, ExecuteMsg, RegistryAddress}, state::TaskId, AppExecuteMsgFns, AppInterface, AppQueryMsgFns};
 use clap::Parser;
 use cosmwasm_std::{BankMsg, Uint128};
 use cw_asset::Asset;
 use cw_orch::{
-    anyhow, daemon::ChainInfo, prelude::{networks::parse_network, DaemonBuilder}, tokio::runtime::Runtime
+    anyhow, daemon::{ChainInfo, ChainKind}, prelude::{networks::parse_network, DaemonBuilder}, tokio::runtime::Runtime
 };
 use cw_orch::prelude::*;
 use abstract_interface::{AppDeployer, DeployStrategy};
 
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// ArchID registry on Archway testnet (constantine)
+const TESTNET_REGISTRY: &str = "archway1lr8rstt40s697hqpedv2nvt27f4cuccqwvly9gnvuszxmcevrlns60xw4r";
+/// ArchID registry on Archway mainnet
+const MAINNET_REGISTRY: &str = "archway1275jwjpktae4y4y0cdq274a2m0jnpekhttnfuljm6n59wnpyd62qppqxq0";
+
+/// ArchID registry the app is installed with on `network`
+fn registry_for(network: &ChainInfo) -> &'static str {
+    match network.kind {
+        ChainKind::Mainnet => MAINNET_REGISTRY,
+        _ => TESTNET_REGISTRY,
+    }
+}
+
+/// Asset the ArchID registry on `network` charges in
+fn registry_asset_for(network: &ChainInfo) -> AssetEntry {
+    match network.kind {
+        ChainKind::Mainnet => AssetEntry::new("archway>arch"),
+        _ => AssetEntry::new("archway>const"),
+    }
+}
+
 fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
     // run for each requested network
     for network in networks {
         // Setup
+        let registry = registry_for(&network);
+        let registry_asset = registry_asset_for(&network);
         let rt = Runtime::new()?;    
         let chain = DaemonBuilder::default()
             .handle(rt.handle())
@@ -57,10 +80,12 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
         let account = abstract_client.account_builder()
             .install_app_with_dependencies::<AppInterface<Daemon>>(
                 &AppInstantiateMsg { 
-                    count: 0,
                     native_asset: AssetEntry::new("osmosis>osmo"),
                     refill_threshold: Uint128::new(0),
                     task_creation_amount: Uint128::new(0),
+                    registry: RegistryAddress::Addr(registry.to_string()),
+                    registry_asset,
+                    max_registration_years: None,
                 },
                 Empty {}
             )?
@@ -71,7 +96,6 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
 
         // let app = account.install_app_with_dependencies::<AppInterface<Daemon>>(
         //     &AppInstantiateMsg { 
-        //         count: 0,
         //         native_asset: AssetEntry::new("osmosis>osmo"),
         //         refill_threshold: Uint128::new(0),
         //         task_creation_amount: Uint128::new(30000000000000000),
@@ -82,7 +106,6 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
 
         // let app = account.install_app_with_dependencies::<AppInterface<Daemon>>(
         //     &AppInstantiateMsg { 
-        //         count: 0,
         //         native_asset: AssetEntry::new("archway>const"),
         //         refill_threshold: Uint128::new(0),
         //         task_creation_amount: Uint128::new(30000000000000000),
@@ -93,7 +116,6 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
         
         // account.install_app::<AppInterface<Daemon>>(
         //     &AppInstantiateMsg { 
-        //         count: 0,
         //         native_asset: AssetEntry::new("archway>const"),
         //         refill_threshold: Uint128::new(0),
         //         task_creation_amount: Uint128::new(30000000000000000),
@@ -108,9 +130,6 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
         //     contract_address
         // );
 
-        // let account = publisher.account();
-        // let app = publisher.account()
-        //     .install_app::<AppInterface<Daemon>>(&AppInstantiateMsg { count: 123 }, &[])?;
         let app = account.application::<AppInterface<Daemon>>()?;
 
         // chain.wallet().
@@ -118,11 +137,9 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
         println!("Account {:?}", account.module_infos());
         println!("Account {:?}", account);
 
-        println!("Incrementing...");
-        app.increment();
-        println!("Count {}", app.count()?.count);
+        println!("Config {:?}", app.config()?);
         
-        // let result = app.register_domain_2("xenos3".to_string())?;
+        // let result = app.register_domain("xenos3".to_string(), None, 1)?;
         // println!("Result {:?}", result);
 
         let result = app.name_resolution("xenos3.arch".to_string())?;
@@ -132,13 +149,13 @@ fn publish(networks: Vec<ChainInfo>) -> anyhow::Result<()> {
 
         // account.manager().execute_on_module(
         //     "abstract",
-        //     Into::<ExecuteMsg>::into(AppExecuteMsg::Increment {}),
+        //     Into::<ExecuteMsg>::into(AppExecuteMsg::ClearDefaultID {}),
         // )?;
 
-        // let result = app.register_domain("xenos1.arch".to_string())?;
+        // let result = app.register_domain("xenos1".to_string(), None, 1)?;
         // println!("Result {:?}", result);
         
-        // let result = app.register_domain_2("xenos1".to_string())?;
+        // let result = app.register_domain("xenos1".to_string(), None, 1)?;
         // println!("Result {:?}", result);
         // let result = app.name_resolution("xenos1.arch".to_string());
         // println!("Result {:?}", result);
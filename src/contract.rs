@@ -3,7 +3,7 @@ use crate::{
     error::AppError,
     handlers,
     msg::{AppExecuteMsg, AppInstantiateMsg, AppQueryMsg},
-    replies::{self, INSTANTIATE_REPLY_ID},
+    replies::{self, INSTANTIATE_REPLY_ID, REFRESH_EXPIRY_REPLY_ID},
 };
 use abstract_app::abstract_core::objects::dependency::StaticDependency;
 use abstract_app::AppContract;
@@ -33,7 +33,10 @@ const APP: App = App::new(APP_ID, APP_VERSION, None)
     .with_execute(handlers::execute_handler)
     .with_query(handlers::query_handler)
     .with_migrate(handlers::migrate_handler)
-    .with_replies(&[(INSTANTIATE_REPLY_ID, replies::instantiate_reply)])
+    .with_replies(&[
+        (INSTANTIATE_REPLY_ID, replies::instantiate_reply),
+        (REFRESH_EXPIRY_REPLY_ID, replies::refresh_expiry_reply),
+    ])
     .with_dependencies(&[
         StaticDependency::new(CRONCAT_ID, &[CRONCAT_MODULE_VERSION]),
     ]);
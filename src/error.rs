@@ -1,9 +1,10 @@
 use abstract_app::abstract_core::AbstractError;
 use abstract_app::abstract_sdk::AbstractSdkError;
 use abstract_app::AppError as AbstractAppError;
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, Coin, OverflowError, StdError, Uint128};
 use cw_asset::AssetError;
 use cw_controllers::AdminError;
+use cw_utils::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -17,6 +18,9 @@ pub enum AppError {
     #[error("{0}")]
     AbstractSdk(#[from] AbstractSdkError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("{0}")]
     Asset(#[from] AssetError),
 
@@ -29,6 +33,61 @@ pub enum AppError {
     #[error("Native asset should be native")]
     NotNativeAsset {},
 
-    #[error("Convert can be called only by the croncat manager {sender} vs {manager}")]
-    NotManagerConvert { sender: Addr, manager: Addr},
+    #[error("Gas limit must be greater than zero")]
+    ZeroGasLimit {},
+
+    #[error("Refill threshold {refill_threshold} exceeds the task creation amount {task_creation_amount}")]
+    RefillThresholdTooHigh {
+        refill_threshold: Uint128,
+        task_creation_amount: Uint128,
+    },
+
+    #[error("At least one domain name is required")]
+    EmptyDomainList {},
+
+    #[error("Invalid domain name {name}: {reason}")]
+    InvalidDomainName { name: String, reason: String },
+
+    #[error("Registration period of {years} years must be between 1 and {max} years")]
+    InvalidRegistrationYears { years: u64, max: u64 },
+
+    #[error("Auto-renewal task {task_id} renews for {years} years, more than the maximum of {max} years")]
+    TaskExceedsMaxYears { task_id: u64, years: u64, max: u64 },
+
+    #[error("Renewal window must be a time duration, got {0}")]
+    InvalidRenewalWindow(Duration),
+
+    #[error("{subdomain} can't expire at {expiration}, after its domain expires at {domain_expiry}")]
+    SubdomainOutlivesDomain {
+        subdomain: String,
+        expiration: u64,
+        domain_expiry: u64,
+    },
+
+    #[error("{sender} does not own {domain}")]
+    NotDomainOwner { domain: String, sender: Addr },
+
+    #[error("Auto-renewal task {task_id} not found")]
+    TaskNotFound { task_id: u64 },
+
+    #[error("{domain} is already renewed by auto-renewal task {task_id}")]
+    DuplicateRenewalTask { domain: String, task_id: u64 },
+
+    #[error("Domain {domain} is not in the domain book")]
+    DomainNotFound { domain: String },
+
+    #[error("Registry {registry} query failed: {reason}")]
+    RegistryError { registry: Addr, reason: String },
+
+    #[error("{sender} is not authorized to {action}")]
+    Unauthorized { sender: Addr, action: String },
+
+    #[error("Insufficient funds: needed {needed}, available {available}")]
+    InsufficientFunds { needed: Coin, available: Coin },
+
+    #[error("Migration requires {field} to be set in the migrate message")]
+    MissingMigrationField { field: String },
+
+    #[error("Renewals can only be triggered by the CronCat manager {manager}, not {sender}")]
+    NotCronCatManager { sender: Addr, manager: Addr },
 }
@@ -0,0 +1,117 @@
+//! Stable event schema for indexers.
+//!
+//! Every state-changing handler emits one `wasm-archid-auto` event per affected domain or task,
+//! next to the regular Abstract response attributes. The event always carries all of the
+//! following attributes, in this order, so indexers can rely on the keys being present:
+//!
+//! | key       | value                                                        |
+//! |-----------|--------------------------------------------------------------|
+//! | `action`  | name of the handler, e.g. `register_domain`                  |
+//! | `domain`  | full domain name (`name.arch`), empty if not domain specific |
+//! | `years`   | registration or renewal period, empty if none                |
+//! | `funds`   | funds paid to the registry or CronCat, empty if none         |
+//! | `task_id` | auto-renewal task id, empty if none                          |
+//! | `expiry`  | domain expiry in seconds, empty if unknown                   |
+//! | `owner`   | domain owner after the action, empty if unchanged            |
+//!
+//! A CronCat run of an auto-renewal task reports its outcome in the action: `renew_domain` when
+//! the renewal was sent, `renew_domain_skipped` when the domain doesn't expire within the task's
+//! renewal window yet, then `refresh_expiry` once the registry granted the new expiry or
+//! `renew_domain_failed` when the registry rejected the renewal.
+
+use cosmwasm_std::{Addr, Coin, Event, Timestamp};
+
+use crate::state::TaskId;
+
+/// Event type, emitted on chain as `wasm-archid-auto`
+pub const EVENT_TYPE: &str = "archid-auto";
+
+/// Builder of the `wasm-archid-auto` event
+pub struct ArchIdEvent {
+    action: String,
+    domain: Option<String>,
+    years: Option<u64>,
+    funds: Option<Coin>,
+    task_id: Option<TaskId>,
+    expiry: Option<Timestamp>,
+    owner: Option<Addr>,
+}
+
+impl ArchIdEvent {
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            domain: None,
+            years: None,
+            funds: None,
+            task_id: None,
+            expiry: None,
+            owner: None,
+        }
+    }
+
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    pub fn years(mut self, years: u64) -> Self {
+        self.years = Some(years);
+        self
+    }
+
+    pub fn funds(mut self, funds: Coin) -> Self {
+        self.funds = Some(funds);
+        self
+    }
+
+    pub fn task_id(mut self, task_id: TaskId) -> Self {
+        self.task_id = Some(task_id);
+        self
+    }
+
+    pub fn expiry(mut self, expiry: Timestamp) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    pub fn owner(mut self, owner: Addr) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+}
+
+impl From<ArchIdEvent> for Event {
+    fn from(event: ArchIdEvent) -> Self {
+        Event::new(EVENT_TYPE).add_attributes([
+            ("action", event.action),
+            ("domain", event.domain.unwrap_or_default()),
+            (
+                "years",
+                event
+                    .years
+                    .map(|years| years.to_string())
+                    .unwrap_or_default(),
+            ),
+            (
+                "funds",
+                event
+                    .funds
+                    .map(|funds| funds.to_string())
+                    .unwrap_or_default(),
+            ),
+            (
+                "task_id",
+                event.task_id.map(|id| id.0.to_string()).unwrap_or_default(),
+            ),
+            (
+                "expiry",
+                event
+                    .expiry
+                    .map(|expiry| expiry.seconds().to_string())
+                    .unwrap_or_default(),
+            ),
+            ("owner", event.owner.map(String::from).unwrap_or_default()),
+        ])
+    }
+}
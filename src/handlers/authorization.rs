@@ -0,0 +1,143 @@
+use abstract_app::abstract_sdk::features::AccountIdentification;
+use cosmwasm_std::{Addr, Deps, Env, MessageInfo};
+use croncat_app::CronCatInterface;
+use cw_controllers::AdminError;
+
+use crate::contract::{App, AppResult};
+use crate::error::AppError;
+use crate::msg::AppExecuteMsg;
+use crate::state::{Role, TaskId, OPERATORS};
+
+/// Who is allowed to send an execute message
+enum Permission {
+    /// The account admin, for anything that spends account funds or changes account domains
+    Admin,
+    /// The account admin or an operator granted the role
+    Operator(Role),
+    /// Like `Operator`, but only the account admin may send the asset to a recipient other than
+    /// the account proxy
+    OperatorToRecipient(Role, Option<String>),
+    /// Any address, for messages that only touch the sender's own entries
+    Sender,
+    /// The CronCat manager running the task
+    CronCatManager(TaskId),
+}
+
+/// Action name and required permission of an execute message
+fn permission(msg: &AppExecuteMsg) -> (&'static str, Permission) {
+    match msg {
+        AppExecuteMsg::UpdateConfig { .. } => ("update_config", Permission::Admin),
+        AppExecuteMsg::GrantRole { .. } => ("grant_role", Permission::Admin),
+        AppExecuteMsg::RevokeRole { .. } => ("revoke_role", Permission::Admin),
+        AppExecuteMsg::UpdateDefaultID { .. } => ("update_default_id", Permission::Sender),
+        AppExecuteMsg::ClearDefaultID {} => ("clear_default_id", Permission::Sender),
+        AppExecuteMsg::RegisterDomain { owner, .. } => (
+            "register_domain",
+            Permission::OperatorToRecipient(Role::Register, owner.clone()),
+        ),
+        AppExecuteMsg::RegisterDomain2 { .. } => (
+            "register_domain",
+            Permission::OperatorToRecipient(Role::Register, None),
+        ),
+        AppExecuteMsg::RegisterDomains { .. } => {
+            ("register_domains", Permission::Operator(Role::Register))
+        }
+        AppExecuteMsg::RenewDomains { .. } => ("renew_domains", Permission::Operator(Role::Renew)),
+        AppExecuteMsg::RenewDomain { task_id } => {
+            ("renew_domain", Permission::CronCatManager(*task_id))
+        }
+        AppExecuteMsg::RegisterSubdomain { new_owner, .. } => (
+            "register_subdomain",
+            Permission::OperatorToRecipient(Role::Register, Some(new_owner.clone())),
+        ),
+        AppExecuteMsg::RemoveSubdomain { .. } => ("remove_subdomain", Permission::Admin),
+        AppExecuteMsg::UpdateResolver { .. } => (
+            "update_resolver",
+            Permission::Operator(Role::UpdateResolver),
+        ),
+        AppExecuteMsg::UpdateMetadata { .. } => ("update_metadata", Permission::Admin),
+        AppExecuteMsg::TransferDomain { .. } => ("transfer_domain", Permission::Admin),
+        AppExecuteMsg::ImportDomain { .. } => {
+            ("import_domain", Permission::Operator(Role::Register))
+        }
+        AppExecuteMsg::CreateAutoRenewalTask { .. } => (
+            "create_auto_renewal_task",
+            Permission::Operator(Role::ManageTasks),
+        ),
+        AppExecuteMsg::CancelAutoRenewalTask { .. } => (
+            "cancel_auto_renewal_task",
+            Permission::Operator(Role::ManageTasks),
+        ),
+        AppExecuteMsg::UpdateAutoRenewalTask { .. } => (
+            "update_auto_renewal_task",
+            Permission::Operator(Role::ManageTasks),
+        ),
+    }
+}
+
+/// Check the sender may execute `msg`, before it is dispatched to its handler
+pub(crate) fn authorize(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    app: &App,
+    msg: &AppExecuteMsg,
+) -> AppResult<()> {
+    let (action, permission) = permission(msg);
+    match permission {
+        Permission::Sender => Ok(()),
+        Permission::Admin => assert_admin_or_role(deps, app, &info.sender, None, action),
+        Permission::Operator(role) => {
+            assert_admin_or_role(deps, app, &info.sender, Some(role), action)
+        }
+        Permission::OperatorToRecipient(role, recipient) => {
+            let leaves_account = match recipient {
+                Some(recipient) => {
+                    deps.api.addr_validate(&recipient)? != app.proxy_address(deps)?
+                }
+                None => false,
+            };
+            let role = (!leaves_account).then_some(role);
+            assert_admin_or_role(deps, app, &info.sender, role, action)
+        }
+        Permission::CronCatManager(task_id) => {
+            let manager = app
+                .cron_cat(deps)
+                .query_manager_addr(env.contract.address.clone(), task_id)?;
+            if manager != info.sender {
+                return Err(AppError::NotCronCatManager {
+                    sender: info.sender.clone(),
+                    manager,
+                });
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check `sender` is the account admin, or an operator granted `role` if one is given
+pub(crate) fn assert_admin_or_role(
+    deps: Deps,
+    app: &App,
+    sender: &Addr,
+    role: Option<Role>,
+    action: &str,
+) -> AppResult<()> {
+    if let Some(role) = role {
+        let roles = OPERATORS
+            .may_load(deps.storage, sender)?
+            .unwrap_or_default();
+        if roles.contains(&role) {
+            return Ok(());
+        }
+    }
+    app.admin
+        .assert_admin(deps, sender)
+        .map_err(|error| match error {
+            AdminError::NotAdmin {} => AppError::Unauthorized {
+                sender: sender.clone(),
+                action: action.to_owned(),
+            },
+            error => error.into(),
+        })
+}
@@ -1,13 +1,35 @@
 use abstract_app::abstract_sdk::AbstractSdkResult;
+use abstract_app::abstract_sdk::features::AccountIdentification;
+use abstract_app::objects::AssetEntry;
 use abstract_app::traits::{AbstractResponse, Execution};
-use cosmwasm_std::{to_json_binary, wasm_execute, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Uint128, WasmMsg};
+use cosmwasm_std::{
+    wasm_execute, Addr, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, ReplyOn,
+    StdResult, Storage, Timestamp, Uint128,
+};
+use archid_registry::msg::MetaDataUpdateMsg;
+use archid_registry::state::Config as RegistryConfig;
+use cw721::Cw721ExecuteMsg;
 use cw_asset::{Asset, AssetList};
+use cw_utils::Duration;
 
 use crate::contract::{App, AppResult};
 
 use crate::error::AppError;
-use crate::msg::{AppExecuteMsg, ExecuteMsg};
-use crate::state::{Config, TaskEntry, TaskId, CONFIG, COUNT, DEFAULT_ID_MAP, NEXT_ID, TASK_LIST};
+use crate::events::ArchIdEvent;
+use crate::handlers::authorization::{assert_admin_or_role, authorize};
+use crate::handlers::instantiate::{resolve_native_denom, validate_config};
+use crate::msg::{AppExecuteMsg, ExecuteMsg, RegistryAddress};
+use crate::registry::{
+    bare_domain_name, full_domain_name, query_domain_owner, query_record_expiration,
+    query_registry_config, registration_cost, registration_period, resolve_registry_addr,
+    validate_years,
+};
+use crate::replies::REFRESH_EXPIRY_REPLY_ID;
+use crate::state::{
+    default_ids, domains, Config, DomainRecord, ExpiryRefresh, Role, SubdomainRecord, TaskEntry,
+    TaskId, CONFIG, NEXT_ID, OPERATORS, PENDING_EXPIRY_REFRESH, PENDING_RENEWAL_TASK, SUBDOMAINS, TASK_LIST,
+};
+use crate::validation::{validate_domain_name, validate_subdomain_label};
 
 use croncat_app::{
     croncat_integration_utils::{CronCatAction, CronCatTaskRequest, CronCatInterval},
@@ -15,6 +37,43 @@ use croncat_app::{
 };
 
 
+/// Optional changes to the app config
+struct ConfigUpdate {
+    native_asset: Option<AssetEntry>,
+    task_creation_amount: Option<Uint128>,
+    refill_threshold: Option<Uint128>,
+    registry: Option<RegistryAddress>,
+    registry_asset: Option<AssetEntry>,
+    default_gas_limit: Option<u64>,
+    default_renewal_years: Option<u64>,
+    max_registration_years: Option<u64>,
+}
+
+/// Subdomain to create under a domain held by the account
+struct SubdomainRegistration {
+    domain: String,
+    subdomain: String,
+    new_resolver: String,
+    new_owner: String,
+    expiration: u64,
+}
+
+/// Auto-renewal task to create
+struct NewTask {
+    frequency: String,
+    domain_name: String,
+    renewal_years: Option<u64>,
+    renew_before: Duration,
+}
+
+/// Optional changes to an auto-renewal task
+struct TaskUpdate {
+    frequency: Option<String>,
+    renewal_years: Option<u64>,
+    gas_limit: Option<u64>,
+    domain_name: Option<String>,
+}
+
 pub fn execute_handler(
     deps: DepsMut,
     env: Env,
@@ -22,15 +81,114 @@ pub fn execute_handler(
     app: App,
     msg: AppExecuteMsg,
 ) -> AppResult {
+    authorize(deps.as_ref(), &env, &info, &app, &msg)?;
+
     match msg {
-        AppExecuteMsg::Increment {} => increment(deps, app),
-        AppExecuteMsg::Reset { count } => reset(deps, info, count, app),
-        AppExecuteMsg::UpdateConfig {} => update_config(deps, info, app),
-        AppExecuteMsg::UpdateDefaultID {} =>update_default_id(deps, info, app),
-        AppExecuteMsg::RegisterDomain { desired_name} => register_domain(deps, info, app, desired_name),
-        AppExecuteMsg::RegisterDomain2 { desired_name} => register_domain2(deps, info, app, desired_name),
-        AppExecuteMsg::RenewDomain { task_id} => renew_domain(deps, env, info, app, task_id),
-        AppExecuteMsg::CreateAutoRenewalTask { frequency, domain_name} => create_auto_renewal_task(deps, env, info, app, frequency, domain_name),
+        AppExecuteMsg::UpdateConfig {
+            native_asset,
+            task_creation_amount,
+            refill_threshold,
+            registry,
+            registry_asset,
+            default_gas_limit,
+            default_renewal_years,
+            max_registration_years,
+        } => update_config(
+            deps,
+            info,
+            app,
+            ConfigUpdate {
+                native_asset,
+                task_creation_amount,
+                refill_threshold,
+                registry,
+                registry_asset,
+                default_gas_limit,
+                default_renewal_years,
+                max_registration_years,
+            },
+        ),
+        AppExecuteMsg::GrantRole { address, role } => grant_role(deps, info, app, address, role),
+        AppExecuteMsg::RevokeRole { address, role } => revoke_role(deps, info, app, address, role),
+        AppExecuteMsg::UpdateDefaultID { name } => update_default_id(deps, info, app, name),
+        AppExecuteMsg::ClearDefaultID {} => clear_default_id(deps, info, app),
+        AppExecuteMsg::RegisterDomain { name, years, owner } => {
+            register_domain(deps, env, info, app, name, years, owner)
+        }
+        AppExecuteMsg::RegisterDomain2 { desired_name } => {
+            register_domain(deps, env, info, app, desired_name, 1, None)
+        }
+        AppExecuteMsg::RenewDomain { task_id } => renew_domain(deps, env, info, app, task_id),
+        AppExecuteMsg::RegisterDomains { names } => register_domains(deps, env, info, app, names),
+        AppExecuteMsg::RenewDomains { names } => renew_domains(deps, env, info, app, names),
+        AppExecuteMsg::RegisterSubdomain {
+            domain,
+            subdomain,
+            new_resolver,
+            new_owner,
+            expiration,
+        } => register_subdomain(
+            deps,
+            info,
+            app,
+            SubdomainRegistration {
+                domain,
+                subdomain,
+                new_resolver,
+                new_owner,
+                expiration,
+            },
+        ),
+        AppExecuteMsg::RemoveSubdomain { domain, subdomain } => {
+            remove_subdomain(deps, info, app, domain, subdomain)
+        }
+        AppExecuteMsg::UpdateResolver { name, new_resolver } => {
+            update_resolver(deps, info, app, name, new_resolver)
+        }
+        AppExecuteMsg::UpdateMetadata { name, metadata } => {
+            update_metadata(deps, info, app, name, metadata)
+        }
+        AppExecuteMsg::TransferDomain { name, recipient } => {
+            transfer_domain(deps, info, app, name, recipient)
+        }
+        AppExecuteMsg::ImportDomain { name } => import_domain(deps, env, info, app, name),
+        AppExecuteMsg::CancelAutoRenewalTask { task_id } => cancel_auto_renewal_task(deps, info, app, task_id),
+        AppExecuteMsg::UpdateAutoRenewalTask {
+            task_id,
+            frequency,
+            renewal_years,
+            gas_limit,
+            domain_name,
+        } => update_auto_renewal_task(
+            deps,
+            env,
+            info,
+            app,
+            task_id,
+            TaskUpdate {
+                frequency,
+                renewal_years,
+                gas_limit,
+                domain_name,
+            },
+        ),
+        AppExecuteMsg::CreateAutoRenewalTask {
+            frequency,
+            domain_name,
+            renewal_years,
+            renew_before,
+        } => create_auto_renewal_task(
+            deps,
+            env,
+            info,
+            app,
+            NewTask {
+                frequency,
+                domain_name,
+                renewal_years,
+                renew_before,
+            },
+        ),
     }
 }
 
@@ -41,6 +199,7 @@ fn create_convert_task_internal(
     task_id: TaskId,
     cron_cat: CronCat<App>,
     config: Config,
+    amount: Uint128,
 ) -> AbstractSdkResult<CosmosMsg> {
     let interval = CronCatInterval::Cron(dca.frequency);
 
@@ -55,110 +214,826 @@ fn create_convert_task_internal(
                 vec![],
             )?
             .into(),
-            gas_limit: Some(300_000),
+            gas_limit: Some(dca.gas_limit),
         }],
         queries: None,
         transforms: None,
         cw20: None,
     };
-    let assets = AssetList::from(vec![Asset::native(
-        config.native_denom,
-        config.task_creation_amount,
-    )])
-    .into();
+    let assets = AssetList::from(vec![Asset::native(config.native_denom, amount)]).into();
 
     cron_cat.create_task(task, task_id, assets)
 }
 
-fn increment(deps: DepsMut, app: App) -> AppResult {
-    COUNT.update(deps.storage, |count| AppResult::Ok(count + 1))?;
+/// Update the configuration of the app
+fn update_config(deps: DepsMut, msg_info: MessageInfo, app: App, update: ConfigUpdate) -> AppResult {
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut attributes = vec![];
+
+    if let Some(native_asset) = update.native_asset {
+        config.native_denom = resolve_native_denom(deps.as_ref(), &app, &native_asset)?;
+        attributes.push(("native_denom", config.native_denom.clone()));
+    }
+    if let Some(task_creation_amount) = update.task_creation_amount {
+        config.task_creation_amount = task_creation_amount;
+        attributes.push(("task_creation_amount", task_creation_amount.to_string()));
+    }
+    if let Some(refill_threshold) = update.refill_threshold {
+        config.refill_threshold = refill_threshold;
+        attributes.push(("refill_threshold", refill_threshold.to_string()));
+    }
+    if let Some(registry) = update.registry {
+        config.registry_addr = resolve_registry_addr(deps.as_ref(), &app, registry)?;
+        attributes.push(("registry_addr", config.registry_addr.to_string()));
+    }
+    if let Some(registry_asset) = update.registry_asset {
+        config.registry_denom = resolve_native_denom(deps.as_ref(), &app, &registry_asset)?;
+        attributes.push(("registry_denom", config.registry_denom.clone()));
+    }
+    if let Some(default_gas_limit) = update.default_gas_limit {
+        config.default_gas_limit = default_gas_limit;
+        attributes.push(("default_gas_limit", default_gas_limit.to_string()));
+    }
+    if let Some(default_renewal_years) = update.default_renewal_years {
+        config.default_renewal_years = default_renewal_years;
+        attributes.push(("default_renewal_years", default_renewal_years.to_string()));
+    }
+    if let Some(max_registration_years) = update.max_registration_years {
+        config.max_registration_years = max_registration_years;
+        attributes.push(("max_registration_years", max_registration_years.to_string()));
+    }
+    validate_config(&config)?;
+    assert_tasks_within_max_years(deps.storage, &config)?;
 
-    Ok(app.response("increment"))
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(app
+        .response("update_config")
+        .add_attribute("sender", msg_info.sender)
+        .add_attributes(attributes)
+        .add_event(ArchIdEvent::new("update_config").into()))
 }
 
-fn reset(deps: DepsMut, info: MessageInfo, count: i32, app: App) -> AppResult {
-    app.admin.assert_admin(deps.as_ref(), &info.sender)?;
-    COUNT.save(deps.storage, &count)?;
+fn grant_role(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    address: String,
+    role: Role,
+) -> AppResult {
+    let operator = deps.api.addr_validate(&address)?;
+    OPERATORS.update(deps.storage, &operator, |roles| -> StdResult<_> {
+        let mut roles = roles.unwrap_or_default();
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+        Ok(roles)
+    })?;
 
-    Ok(app.response("reset"))
+    Ok(app
+        .response("grant_role")
+        .add_event(ArchIdEvent::new("grant_role").into())
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("operator", operator)
+        .add_attribute("role", role.to_string()))
 }
 
-/// Update the configuration of the app
-fn update_config(deps: DepsMut, msg_info: MessageInfo, app: App) -> AppResult {
-    // Only the admin should be able to call this
-    app.admin.assert_admin(deps.as_ref(), &msg_info.sender)?;
-    let mut _config = CONFIG.load(deps.storage)?;
+fn revoke_role(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    address: String,
+    role: Role,
+) -> AppResult {
+    let operator = deps.api.addr_validate(&address)?;
+    let mut roles = OPERATORS
+        .may_load(deps.storage, &operator)?
+        .unwrap_or_default();
+    roles.retain(|granted| *granted != role);
+    if roles.is_empty() {
+        OPERATORS.remove(deps.storage, &operator);
+    } else {
+        OPERATORS.save(deps.storage, &operator, &roles)?;
+    }
 
-    Ok(app.response("update_config"))
+    Ok(app
+        .response("revoke_role")
+        .add_event(ArchIdEvent::new("revoke_role").into())
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("operator", operator)
+        .add_attribute("role", role.to_string()))
 }
 
-fn update_default_id(deps: DepsMut, msg_info: MessageInfo, app: App) -> AppResult {
-    let value = "hello.arch".to_string();
-    let _ = DEFAULT_ID_MAP.save(deps.storage, msg_info.sender.to_owned(), &value);
+/// Set the sender's default ID to a domain owned by the sender, or owned by the account proxy if
+/// the sender is the admin or an operator allowed to set default IDs
+fn update_default_id(deps: DepsMut, msg_info: MessageInfo, app: App, name: String) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&name)?;
+
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+    let owner = query_domain_owner(deps.as_ref(), &registry_config.cw721, &domain)?;
+    let proxy = app.proxy_address(deps.as_ref())?;
+    if owner != msg_info.sender {
+        if owner != proxy {
+            return Err(AppError::NotDomainOwner {
+                domain,
+                sender: msg_info.sender,
+            });
+        }
+        // Account domains are only handed out as default IDs by the admin or its operators
+        assert_admin_or_role(
+            deps.as_ref(),
+            &app,
+            &msg_info.sender,
+            Some(Role::SetDefaultId),
+            "update_default_id",
+        )?;
+    }
 
-    // TODO: Only the owner can do this.
+    default_ids().save(deps.storage, msg_info.sender.clone(), &domain)?;
 
     Ok(app
         .response("update_default_id")
-        .add_attribute("default_id", value)
+        .add_event(ArchIdEvent::new("update_default_id").domain(&domain).into())
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("default_id", domain)
     )
 }
 
-fn register_domain(deps: DepsMut, msg_info: MessageInfo, app: App, desired_name: String) -> AppResult {
-
-    let registry_contract = "archway1lr8rstt40s697hqpedv2nvt27f4cuccqwvly9gnvuszxmcevrlns60xw4r";
+fn clear_default_id(deps: DepsMut, msg_info: MessageInfo, app: App) -> AppResult {
+    default_ids().remove(deps.storage, msg_info.sender.clone())?;
 
-    let cost_per_year: u128 = 1000000000000000000;
-    let denom = "aarch"; // (Or "aconst" for testnet)
+    Ok(app
+        .response("clear_default_id")
+        .add_event(ArchIdEvent::new("clear_default_id").into())
+        .add_attribute("sender", msg_info.sender))
+}
 
-    let register_msg = archid_registry::msg::ExecuteMsg::Register {
-        name: desired_name,
-    };
+fn register_domain(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    app: App,
+    name: String,
+    years: u64,
+    owner: Option<String>,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+    let priced = price_domain(&config, &registry_config, &name, years)?;
 
-    let register_resp =  WasmMsg::Execute {
-        contract_addr: registry_contract.into(),
-        msg: to_json_binary(&register_msg)?,
-        funds: vec![Coin {
-            denom: "aconst".into(),
-            amount: Uint128::from(cost_per_year),
-        }]
-    };
+    let proxy = app.proxy_address(deps.as_ref())?;
+    let owner = owner
+        .map(|owner| deps.api.addr_validate(&owner))
+        .transpose()?
+        .unwrap_or_else(|| proxy.clone());
 
-    let messages = vec![register_resp];
+    let (actions, refresh) = pay_registry(
+        deps.storage,
+        &env,
+        &config,
+        &registry_config,
+        &proxy,
+        RegistryPayment::Registration { owner: &owner },
+        &priced,
+    )?;
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute_with_reply(
+        actions.into_iter().map(Into::into).collect(),
+        ReplyOn::Success,
+        REFRESH_EXPIRY_REPLY_ID,
+    )?;
+    PENDING_EXPIRY_REFRESH.save(deps.storage, &vec![refresh])?;
 
+    let PricedDomain {
+        domain,
+        years,
+        funds,
+    } = priced;
     Ok(
-        app.response("register_domain").add_messages(messages).add_attribute("sender", msg_info.sender)
+        app.response("register_domain")
+            .add_submessage(account_message)
+            .add_event(
+                ArchIdEvent::new("register_domain")
+                    .domain(&domain)
+                    .years(years)
+                    .funds(funds.clone())
+                    .owner(owner.clone())
+                    .into(),
+            )
+            .add_attribute("sender", msg_info.sender)
+            .add_attribute("domain", domain)
+            .add_attribute("years", years.to_string())
+            .add_attribute("funds", funds.to_string())
+            .add_attribute("owner", owner)
     )
 }
 
-fn register_domain2(deps: DepsMut, msg_info: MessageInfo, app: App, desired_name: String) -> AppResult {
+fn register_domains(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    app: App,
+    names: Vec<(String, u64)>,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+
+    let priced = price_domains(&config, &registry_config, names)?;
+    let proxy = app.proxy_address(deps.as_ref())?;
+    assert_proxy_balance(deps.as_ref(), &config, &proxy, &priced)?;
 
-    let registry_contract = "archway1lr8rstt40s697hqpedv2nvt27f4cuccqwvly9gnvuszxmcevrlns60xw4r";
+    let mut response = app
+        .response("register_domains")
+        .add_attribute("sender", msg_info.sender);
+    let mut actions: Vec<CosmosMsg> = Vec::with_capacity(priced.len());
+    let mut refreshes = Vec::with_capacity(priced.len());
+    for priced in priced {
+        let (domain_actions, refresh) = pay_registry(
+            deps.storage,
+            &env,
+            &config,
+            &registry_config,
+            &proxy,
+            RegistryPayment::Registration { owner: &proxy },
+            &priced,
+        )?;
+        actions.extend(domain_actions);
+        refreshes.push(refresh);
 
-    let cost_per_year: u128 = 1000000000000000000;
-    let denom = "aarch"; // (Or "aconst" for testnet)
+        let PricedDomain {
+            domain,
+            years,
+            funds,
+        } = priced;
+        response = response
+            .add_event(
+                ArchIdEvent::new("register_domains")
+                    .domain(&domain)
+                    .years(years)
+                    .funds(funds.clone())
+                    .owner(proxy.clone())
+                    .into(),
+            )
+            .add_attributes(vec![
+                ("domain", domain),
+                ("years", years.to_string()),
+                ("funds", funds.to_string()),
+            ]);
+    }
 
-    let register_msg = archid_registry::msg::ExecuteMsg::Register {
-        name: desired_name,
-    };
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute_with_reply(
+        actions.into_iter().map(Into::into).collect(),
+        ReplyOn::Success,
+        REFRESH_EXPIRY_REPLY_ID,
+    )?;
+    PENDING_EXPIRY_REFRESH.save(deps.storage, &refreshes)?;
+
+    Ok(response.add_submessage(account_message))
+}
+
+fn renew_domains(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    app: App,
+    names: Vec<(String, u64)>,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+
+    let priced = price_domains(&config, &registry_config, names)?;
+    let proxy = app.proxy_address(deps.as_ref())?;
+    assert_proxy_balance(deps.as_ref(), &config, &proxy, &priced)?;
+
+    let mut response = app
+        .response("renew_domains")
+        .add_attribute("sender", msg_info.sender);
+    let mut actions: Vec<CosmosMsg> = Vec::with_capacity(priced.len());
+    let mut refreshes = Vec::with_capacity(priced.len());
+    for priced in priced {
+        let expiry = current_expiry(deps.as_ref(), &config, &priced.domain)?;
+        let (domain_actions, refresh) = pay_registry(
+            deps.storage,
+            &env,
+            &config,
+            &registry_config,
+            &proxy,
+            RegistryPayment::Renewal { expiry },
+            &priced,
+        )?;
+        actions.extend(domain_actions);
+        refreshes.push(refresh);
+
+        let PricedDomain {
+            domain,
+            years,
+            funds,
+        } = priced;
+        response = response
+            .add_event(
+                ArchIdEvent::new("renew_domains")
+                    .domain(&domain)
+                    .years(years)
+                    .funds(funds.clone())
+                    .into(),
+            )
+            .add_attributes(vec![
+                ("domain", domain),
+                ("years", years.to_string()),
+                ("funds", funds.to_string()),
+            ]);
+    }
+
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute_with_reply(
+        actions.into_iter().map(Into::into).collect(),
+        ReplyOn::Success,
+        REFRESH_EXPIRY_REPLY_ID,
+    )?;
+    PENDING_EXPIRY_REFRESH.save(deps.storage, &refreshes)?;
+
+    Ok(response.add_submessage(account_message))
+}
+
+/// Domain priced for a registration or renewal
+struct PricedDomain {
+    /// Full domain name
+    domain: String,
+    years: u64,
+    funds: Coin,
+}
+
+/// What a registry payment buys
+enum RegistryPayment<'a> {
+    /// Register the domain and hand its NFT to `owner`
+    Registration { owner: &'a Addr },
+    /// Extend the registration of a domain expiring at `expiry`
+    Renewal { expiry: Timestamp },
+}
+
+/// Messages paying the registry for `priced`, and the expiry refresh the reply applies once they
+/// succeed. Registered domains enter the domain book right away.
+fn pay_registry(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    registry_config: &RegistryConfig,
+    proxy: &Addr,
+    payment: RegistryPayment,
+    priced: &PricedDomain,
+) -> AppResult<(Vec<CosmosMsg>, ExpiryRefresh)> {
+    let PricedDomain {
+        domain,
+        years,
+        funds,
+    } = priced;
+    let name = bare_domain_name(domain).to_string();
+    let period = registration_period(registry_config, *years);
+
+    match payment {
+        RegistryPayment::Registration { owner } => {
+            let mut actions: Vec<CosmosMsg> = vec![wasm_execute(
+                config.registry_addr.to_string(),
+                &archid_registry::msg::ExecuteMsg::Register { name },
+                vec![funds.clone()],
+            )?
+            .into()];
+
+            // The registry mints the domain NFT to the proxy, hand it over if someone else should
+            // own it
+            if owner != proxy {
+                actions.push(
+                    wasm_execute(
+                        registry_config.cw721.to_string(),
+                        &Cw721ExecuteMsg::TransferNft {
+                            recipient: owner.to_string(),
+                            token_id: domain.clone(),
+                        },
+                        vec![],
+                    )?
+                    .into(),
+                );
+            }
+
+            domains().save(
+                storage,
+                domain,
+                &DomainRecord {
+                    name: domain.clone(),
+                    owner: owner.clone(),
+                    token_id: domain.clone(),
+                    registered_height: env.block.height,
+                    registered_time: env.block.time,
+                    // Refreshed from the registry once the registration succeeded
+                    expiry: env.block.time,
+                    renewal_task: None,
+                },
+            )?;
+
+            let refresh = ExpiryRefresh {
+                domain: domain.clone(),
+                min_expiry: env.block.time.plus_seconds(period),
+            };
+            Ok((actions, refresh))
+        }
+        RegistryPayment::Renewal { expiry } => {
+            let action: CosmosMsg = wasm_execute(
+                config.registry_addr.to_string(),
+                &archid_registry::msg::ExecuteMsg::RenewRegistration { name },
+                vec![funds.clone()],
+            )?
+            .into();
+
+            let refresh = ExpiryRefresh {
+                domain: domain.clone(),
+                min_expiry: expiry.plus_seconds(period),
+            };
+            Ok((vec![action], refresh))
+        }
+    }
+}
 
-    let register_resp =  WasmMsg::Execute {
-        contract_addr: registry_contract.into(),
-        msg: to_json_binary(&register_msg)?,
-        funds: vec![Coin {
-            denom: "aconst".into(),
-            amount: Uint128::from(cost_per_year),
-        }]
+/// Expiry of `domain` from the domain book, or from the registry if the app doesn't track it
+fn current_expiry(deps: Deps, config: &Config, domain: &str) -> AppResult<Timestamp> {
+    match domains().may_load(deps.storage, domain)? {
+        Some(record) => Ok(record.expiry),
+        None => query_record_expiration(deps, &config.registry_addr, domain),
+    }
+}
+
+/// Normalize and price `name` for `years`
+fn price_domain(
+    config: &Config,
+    registry_config: &RegistryConfig,
+    name: &str,
+    years: u64,
+) -> AppResult<PricedDomain> {
+    let domain = validate_domain_name(name)?;
+    let funds = registration_cost(config, registry_config, &domain, years)?;
+    Ok(PricedDomain {
+        domain,
+        years,
+        funds,
+    })
+}
+
+/// Normalize and price each `(name, years)` pair, failing on the first invalid one
+fn price_domains(
+    config: &Config,
+    registry_config: &RegistryConfig,
+    names: Vec<(String, u64)>,
+) -> AppResult<Vec<PricedDomain>> {
+    if names.is_empty() {
+        return Err(AppError::EmptyDomainList {});
+    }
+    names
+        .into_iter()
+        .map(|(name, years)| price_domain(config, registry_config, &name, years))
+        .collect()
+}
+
+/// Check the proxy holds enough of the registry denom to pay for every priced domain
+fn assert_proxy_balance(
+    deps: Deps,
+    config: &Config,
+    proxy: &Addr,
+    priced: &[PricedDomain],
+) -> AppResult<()> {
+    let needed = priced
+        .iter()
+        .try_fold(Uint128::zero(), |total, priced| total.checked_add(priced.funds.amount))?;
+    let available = deps.querier.query_balance(proxy, &config.registry_denom)?;
+    if available.amount < needed {
+        return Err(AppError::InsufficientFunds {
+            needed: Coin {
+                denom: config.registry_denom.clone(),
+                amount: needed,
+            },
+            available,
+        });
+    }
+    Ok(())
+}
+
+fn register_subdomain(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    registration: SubdomainRegistration,
+) -> AppResult {
+    let SubdomainRegistration {
+        domain,
+        subdomain,
+        new_resolver,
+        new_owner,
+        expiration,
+    } = registration;
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&domain)?;
+    validate_subdomain_label(&subdomain)?;
+    // Only subdomains of domains in the domain book are managed by the app
+    let record = domains()
+        .may_load(deps.storage, &domain)?
+        .ok_or_else(|| AppError::DomainNotFound {
+            domain: domain.clone(),
+        })?;
+    if expiration > record.expiry.seconds() {
+        return Err(AppError::SubdomainOutlivesDomain {
+            subdomain: format!("{subdomain}.{domain}"),
+            expiration,
+            domain_expiry: record.expiry.seconds(),
+        });
+    }
+    let expiry = Timestamp::from_seconds(expiration);
+
+    let new_resolver = deps.api.addr_validate(&new_resolver)?;
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+
+    let register_msg: CosmosMsg = wasm_execute(
+        config.registry_addr.to_string(),
+        &archid_registry::msg::ExecuteMsg::RegisterSubdomain {
+            domain: bare_domain_name(&domain).to_string(),
+            subdomain: subdomain.clone(),
+            new_resolver: new_resolver.clone(),
+            new_owner: new_owner.clone(),
+            expiration,
+        },
+        vec![],
+    )?
+    .into();
+
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute(vec![register_msg.into()])?;
+
+    let name = format!("{subdomain}.{domain}");
+    SUBDOMAINS.save(
+        deps.storage,
+        (&domain, &subdomain),
+        &SubdomainRecord {
+            name: name.clone(),
+            owner: new_owner.clone(),
+            resolver: new_resolver,
+            expiry,
+        },
+    )?;
+
+    Ok(app
+        .response("register_subdomain")
+        .add_message(account_message)
+        .add_event(
+            ArchIdEvent::new("register_subdomain")
+                .domain(&name)
+                .expiry(expiry)
+                .owner(new_owner.clone())
+                .into(),
+        )
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("domain", name)
+        .add_attribute("owner", new_owner)
+        .add_attribute("expiry", expiration.to_string()))
+}
+
+fn remove_subdomain(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    domain: String,
+    subdomain: String,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&domain)?;
+    validate_subdomain_label(&subdomain)?;
+
+    let remove_msg: CosmosMsg = wasm_execute(
+        config.registry_addr.to_string(),
+        &archid_registry::msg::ExecuteMsg::RemoveSubdomain {
+            domain: bare_domain_name(&domain).to_string(),
+            subdomain: subdomain.clone(),
+        },
+        vec![],
+    )?
+    .into();
+
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute(vec![remove_msg.into()])?;
+
+    SUBDOMAINS.remove(deps.storage, (&domain, &subdomain));
+
+    Ok(app
+        .response("remove_subdomain")
+        .add_message(account_message)
+        .add_event(
+            ArchIdEvent::new("remove_subdomain")
+                .domain(format!("{subdomain}.{domain}"))
+                .into(),
+        )
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("domain", format!("{subdomain}.{domain}")))
+}
+
+fn update_resolver(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    name: String,
+    new_resolver: Option<String>,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&name)?;
+    let new_resolver = match new_resolver {
+        Some(resolver) => deps.api.addr_validate(&resolver)?,
+        None => app.proxy_address(deps.as_ref())?,
     };
 
+    let update_msg: CosmosMsg = wasm_execute(
+        config.registry_addr.to_string(),
+        &archid_registry::msg::ExecuteMsg::UpdateResolver {
+            name: bare_domain_name(&domain).to_string(),
+            new_resolver: new_resolver.clone(),
+        },
+        vec![],
+    )?
+    .into();
+
     let executor = app.executor(deps.as_ref());
-    let account_message = executor.execute(vec![register_resp.into()]).unwrap();
+    let account_message = executor.execute(vec![update_msg.into()])?;
 
-    Ok(
-        app.response("register_domain")
-            .add_message(account_message)
-            .add_attribute("sender", msg_info.sender)
-    )
+    Ok(app
+        .response("update_resolver")
+        .add_message(account_message)
+        .add_event(ArchIdEvent::new("update_resolver").domain(&domain).into())
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("domain", domain)
+        .add_attribute("resolver", new_resolver))
+}
+
+fn update_metadata(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    name: String,
+    metadata: MetaDataUpdateMsg,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&name)?;
+
+    // The registry forwards the update to the domain NFT's `UpdateMetadata`
+    let update_msg: CosmosMsg = wasm_execute(
+        config.registry_addr.to_string(),
+        &archid_registry::msg::ExecuteMsg::UpdateUserDomainData {
+            name: bare_domain_name(&domain).to_string(),
+            metadata_update: metadata,
+        },
+        vec![],
+    )?
+    .into();
+
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute(vec![update_msg.into()])?;
+
+    Ok(app
+        .response("update_metadata")
+        .add_message(account_message)
+        .add_event(ArchIdEvent::new("update_metadata").domain(&domain).into())
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("domain", domain))
+}
+
+fn transfer_domain(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    name: String,
+    recipient: String,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&name)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let record = domains().may_load(deps.storage, &domain)?;
+    let token_id = record
+        .as_ref()
+        .map(|record| record.token_id.clone())
+        .unwrap_or_else(|| domain.clone());
+
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+    let transfer_msg: CosmosMsg = wasm_execute(
+        registry_config.cw721,
+        &Cw721ExecuteMsg::TransferNft {
+            recipient: recipient.to_string(),
+            token_id,
+        },
+        vec![],
+    )?
+    .into();
+
+    let executor = app.executor(deps.as_ref());
+    let account_message = executor.execute(vec![transfer_msg.into()])?;
+    let mut response = app
+        .response("transfer_domain")
+        .add_message(account_message)
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("domain", domain.clone())
+        .add_attribute("owner", recipient.clone());
+    let event = ArchIdEvent::new("transfer_domain")
+        .domain(&domain)
+        .owner(recipient);
+
+    // The account can't renew a domain it no longer holds
+    let task_ids = renewal_tasks(deps.storage, &domain)?;
+    for task_id in &task_ids {
+        TASK_LIST.remove(deps.storage, *task_id);
+    }
+    let cron_cat = app.cron_cat(deps.as_ref());
+    for task_id in task_ids {
+        response = response
+            .add_message(cron_cat.remove_task(task_id)?)
+            .add_event(
+                ArchIdEvent::new("cancel_auto_renewal_task")
+                    .domain(&domain)
+                    .task_id(task_id)
+                    .into(),
+            )
+            .add_attribute("task_id", task_id.0.to_string());
+    }
+
+    let default_id_holders = default_ids()
+        .idx
+        .name
+        .prefix(domain.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for address in default_id_holders {
+        default_ids().remove(deps.storage, address)?;
+    }
+
+    let subdomains = SUBDOMAINS
+        .prefix(&domain)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for subdomain in subdomains {
+        SUBDOMAINS.remove(deps.storage, (&domain, &subdomain));
+    }
+    domains().remove(deps.storage, &domain)?;
+
+    Ok(response.add_event(event.into()))
+}
+
+fn import_domain(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    app: App,
+    name: String,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let domain = validate_domain_name(&name)?;
+
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+    let owner = query_domain_owner(deps.as_ref(), &registry_config.cw721, &domain)?;
+    let proxy = app.proxy_address(deps.as_ref())?;
+    if owner != proxy {
+        return Err(AppError::NotDomainOwner {
+            domain,
+            sender: proxy,
+        });
+    }
+    let expiry = query_record_expiration(deps.as_ref(), &config.registry_addr, &domain)?;
+
+    // A domain already in the domain book keeps its registration data and renewal task
+    let record = match domains().may_load(deps.storage, &domain)? {
+        Some(record) => DomainRecord {
+            owner: owner.clone(),
+            expiry,
+            ..record
+        },
+        None => DomainRecord {
+            name: domain.clone(),
+            owner: owner.clone(),
+            token_id: domain.clone(),
+            // Registration happened outside the app, track the import instead
+            registered_height: env.block.height,
+            registered_time: env.block.time,
+            expiry,
+            renewal_task: None,
+        },
+    };
+    domains().save(deps.storage, &domain, &record)?;
+
+    Ok(app
+        .response("import_domain")
+        .add_event(
+            ArchIdEvent::new("import_domain")
+                .domain(&domain)
+                .expiry(expiry)
+                .owner(owner.clone())
+                .into(),
+        )
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("domain", domain)
+        .add_attribute("owner", owner)
+        .add_attribute("expiry", expiry.seconds().to_string()))
 }
 
 // To be called by cron-cat only
@@ -169,40 +1044,99 @@ fn renew_domain(
     app: App,
     task_id: TaskId) -> AppResult
 {
-    let registry_contract = "archway1lr8rstt40s697hqpedv2nvt27f4cuccqwvly9gnvuszxmcevrlns60xw4r";
+    let config = CONFIG.load(deps.storage)?;
 
     let cron_cat = app.cron_cat(deps.as_ref());
 
-    let manager_addr = cron_cat.query_manager_addr(env.contract.address.clone(), task_id)?;
-    if manager_addr != msg_info.sender {
-        return Err(AppError::NotManagerConvert { sender: msg_info.sender, manager: manager_addr });
-    }
+    // Check if task balance needs refill
+    let task_balance = cron_cat
+        .query_task_balance(env.contract.address.clone(), task_id)?
+        .balance
+        .map(|balance| balance.native_balance)
+        .unwrap_or_default();
+    let refill_msg = if task_balance < config.refill_threshold {
+        Some(cron_cat.refill_task(
+            task_id,
+            AssetList::from(vec![Asset::native(
+                config.native_denom.clone(),
+                config.task_creation_amount,
+            )])
+            .into(),
+        )?)
+    } else {
+        None
+    };
 
-    let cost_per_year: u128 = 1000000000000000000;
-    let denom = "aarch"; // (Or "aconst" for testnet)
+    let mut task_entry = TASK_LIST
+        .may_load(deps.storage, task_id)?
+        .ok_or(AppError::TaskNotFound { task_id: task_id.0 })?;
+    task_entry.last_run = Some(env.block.time);
+    task_entry.run_count += 1;
+    TASK_LIST.save(deps.storage, task_id, &task_entry)?;
 
-    let task_entry = TASK_LIST.load(deps.storage, task_id)?;
+    let domain = full_domain_name(&task_entry.domain_name);
 
-    let renew_msg = archid_registry::msg::ExecuteMsg::RenewRegistration {
-        name: task_entry.domain_name,
+    // Renewing is a no-op until the domain expires within the task's window
+    let expiry = current_expiry(deps.as_ref(), &config, &domain)?;
+    let renew_from = match task_entry.renew_before {
+        Duration::Time(seconds) => Timestamp::from_seconds(expiry.seconds().saturating_sub(seconds)),
+        Duration::Height(_) => return Err(AppError::InvalidRenewalWindow(task_entry.renew_before)),
     };
+    if env.block.time < renew_from {
+        return Ok(app
+            .response("renew_domain_skipped")
+            .add_messages(refill_msg)
+            .add_event(
+                ArchIdEvent::new("renew_domain_skipped")
+                    .domain(&domain)
+                    .task_id(task_id)
+                    .expiry(expiry)
+                    .into(),
+            )
+            .add_attribute("sender", msg_info.sender)
+            .add_attribute("domain", domain)
+            .add_attribute("renewed", "false")
+            .add_attribute("expiry", expiry.seconds().to_string()));
+    }
 
-    let renew_resp =  WasmMsg::Execute {
-        contract_addr: registry_contract.into(),
-        msg: to_json_binary(&renew_msg)?,
-        funds: vec![Coin {
-            denom: "aconst".into(),
-            amount: Uint128::from(cost_per_year),
-        }]
-    };
+    let registry_config = query_registry_config(deps.as_ref(), &config.registry_addr)?;
+    let priced = price_domain(&config, &registry_config, &domain, task_entry.renewal_years)?;
+    let proxy = app.proxy_address(deps.as_ref())?;
+    let (actions, refresh) = pay_registry(
+        deps.storage,
+        &env,
+        &config,
+        &registry_config,
+        &proxy,
+        RegistryPayment::Renewal { expiry },
+        &priced,
+    )?;
 
     let executor = app.executor(deps.as_ref());
-    let account_message = executor.execute(vec![renew_resp.into()]).unwrap();
+    // Reply on failure too, so the error is kept on the task instead of failing the CronCat run
+    let account_message = executor.execute_with_reply(
+        actions.into_iter().map(Into::into).collect(),
+        ReplyOn::Always,
+        REFRESH_EXPIRY_REPLY_ID,
+    )?;
+    PENDING_EXPIRY_REFRESH.save(deps.storage, &vec![refresh])?;
+    PENDING_RENEWAL_TASK.save(deps.storage, &task_id)?;
 
     Ok(
         app.response("renew_domain")
-            .add_message(account_message)
+            .add_messages(refill_msg)
+            .add_submessage(account_message)
+            .add_event(
+                ArchIdEvent::new("renew_domain")
+                    .domain(&domain)
+                    .years(task_entry.renewal_years)
+                    .funds(priced.funds)
+                    .task_id(task_id)
+                    .into(),
+            )
             .add_attribute("sender", msg_info.sender)
+            .add_attribute("domain", domain)
+            .add_attribute("renewed", "true")
     )
 
 }
@@ -213,31 +1147,228 @@ fn create_auto_renewal_task(
     env: Env,
     msg_info: MessageInfo,
     app: App,
-    frequency: String,
-    domain_name: String) -> AppResult
-{
-
-    app.admin.assert_admin(deps.as_ref(), &msg_info.sender)?;
+    new_task: NewTask,
+) -> AppResult {
+    let NewTask {
+        frequency,
+        domain_name,
+        renewal_years,
+        renew_before,
+    } = new_task;
+    if let Duration::Height(_) = renew_before {
+        return Err(AppError::InvalidRenewalWindow(renew_before));
+    }
 
     let config = CONFIG.load(deps.storage)?;
+    let domain_name = validate_domain_name(&domain_name)?;
+    let renewal_years = renewal_years.unwrap_or(config.default_renewal_years);
+    validate_years(&config, renewal_years)?;
+    assert_no_renewal_task(deps.storage, &domain_name)?;
     let task_id = NEXT_ID.update(deps.storage, |id| AppResult::Ok(id.next_id()))?;
+    set_renewal_task(deps.storage, &domain_name, Some(task_id))?;
+
+    let amount = config.task_creation_amount;
     let task_entry = TaskEntry {
+        creator: msg_info.sender.clone(),
+        created_at: env.block.time,
         frequency,
-        domain_name
+        domain_name,
+        renewal_years,
+        renew_before,
+        gas_limit: config.default_gas_limit,
+        funding: amount,
+        last_run: None,
+        run_count: 0,
+        last_error: None,
     };
-    let cron_cat = app.cron_cat(deps.as_ref());
+    TASK_LIST.save(deps.storage, task_id, &task_entry)?;
 
-    let task_msg = create_convert_task_internal(env, task_entry, task_id, cron_cat, config)?;
+    let cron_cat = app.cron_cat(deps.as_ref());
+    let event = ArchIdEvent::new("create_auto_renewal_task")
+        .domain(&task_entry.domain_name)
+        .years(task_entry.renewal_years)
+        .funds(Coin {
+            denom: config.native_denom.clone(),
+            amount,
+        })
+        .task_id(task_id);
+    let task_msg = create_convert_task_internal(env, task_entry, task_id, cron_cat, config, amount)?;
 
     Ok(
         app.response("create_auto_renewal_task")
             .add_message(task_msg)
+            .add_event(event.into())
             .add_attribute("sender", msg_info.sender)
+            .add_attribute("task_id", task_id.0.to_string())
     )
 }
 
 // Cancel auto-extend + refund
+fn cancel_auto_renewal_task(
+    deps: DepsMut,
+    msg_info: MessageInfo,
+    app: App,
+    task_id: TaskId,
+) -> AppResult {
+    let task_entry = TASK_LIST
+        .may_load(deps.storage, task_id)?
+        .ok_or(AppError::TaskNotFound { task_id: task_id.0 })?;
+    TASK_LIST.remove(deps.storage, task_id);
+
+    let domain = full_domain_name(&task_entry.domain_name);
+    if let Some(mut record) = domains().may_load(deps.storage, &domain)? {
+        if record.renewal_task == Some(task_id) {
+            record.renewal_task = None;
+            domains().save(deps.storage, &domain, &record)?;
+        }
+    }
+
+    // CronCat refunds the remaining task balance to the account proxy
+    let cron_cat = app.cron_cat(deps.as_ref());
+    let remove_task_msg = cron_cat.remove_task(task_id)?;
+
+    Ok(app
+        .response("cancel_auto_renewal_task")
+        .add_message(remove_task_msg)
+        .add_event(
+            ArchIdEvent::new("cancel_auto_renewal_task")
+                .domain(&domain)
+                .task_id(task_id)
+                .into(),
+        )
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("task_id", task_id.0.to_string())
+        .add_attribute("domain", domain))
+}
 
 // Update auto-extend
+fn update_auto_renewal_task(
+    deps: DepsMut,
+    env: Env,
+    msg_info: MessageInfo,
+    app: App,
+    task_id: TaskId,
+    update: TaskUpdate,
+) -> AppResult {
+    let config = CONFIG.load(deps.storage)?;
+    let mut task_entry = TASK_LIST
+        .may_load(deps.storage, task_id)?
+        .ok_or(AppError::TaskNotFound { task_id: task_id.0 })?;
+
+    if let Some(frequency) = update.frequency {
+        task_entry.frequency = frequency;
+    }
+    if let Some(renewal_years) = update.renewal_years {
+        validate_years(&config, renewal_years)?;
+        task_entry.renewal_years = renewal_years;
+    }
+    if let Some(gas_limit) = update.gas_limit {
+        if gas_limit == 0 {
+            return Err(AppError::ZeroGasLimit {});
+        }
+        task_entry.gas_limit = gas_limit;
+    }
+    if let Some(domain_name) = update.domain_name {
+        let domain_name = validate_domain_name(&domain_name)?;
+        if domain_name != full_domain_name(&task_entry.domain_name) {
+            assert_no_renewal_task(deps.storage, &domain_name)?;
+        }
+        set_renewal_task(deps.storage, &task_entry.domain_name, None)?;
+        set_renewal_task(deps.storage, &domain_name, Some(task_id))?;
+        task_entry.domain_name = domain_name;
+    }
+
+    let cron_cat = app.cron_cat(deps.as_ref());
+
+    // Carry the leftover balance of the old task over to the new one
+    let leftover = cron_cat
+        .query_task_balance(env.contract.address.clone(), task_id)?
+        .balance
+        .map(|balance| balance.native_balance)
+        .unwrap_or_default();
+    let amount = if leftover.is_zero() {
+        config.task_creation_amount
+    } else {
+        leftover
+    };
+    task_entry.funding = amount;
+    TASK_LIST.save(deps.storage, task_id, &task_entry)?;
+
+    // Removing the task refunds its balance to the proxy, the new task is then funded from it
+    let remove_task_msg = cron_cat.remove_task(task_id)?;
+    let event = ArchIdEvent::new("update_auto_renewal_task")
+        .domain(full_domain_name(&task_entry.domain_name))
+        .years(task_entry.renewal_years)
+        .funds(Coin {
+            denom: config.native_denom.clone(),
+            amount,
+        })
+        .task_id(task_id);
+    let create_task_msg =
+        create_convert_task_internal(env, task_entry.clone(), task_id, cron_cat, config, amount)?;
+
+    Ok(app
+        .response("update_auto_renewal_task")
+        .add_messages(vec![remove_task_msg, create_task_msg])
+        .add_event(event.into())
+        .add_attribute("sender", msg_info.sender)
+        .add_attribute("task_id", task_id.0.to_string())
+        .add_attribute("domain", full_domain_name(&task_entry.domain_name))
+        .add_attribute("frequency", task_entry.frequency))
+}
+
+/// Auto-renewal tasks renewing `domain`, whether or not it is in the domain book
+fn renewal_tasks(storage: &dyn Storage, domain: &str) -> AppResult<Vec<TaskId>> {
+    let task_ids = TASK_LIST
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, task)| full_domain_name(&task.domain_name) == domain)
+        })
+        .map(|item| item.map(|(id, _)| TaskId(id)))
+        .collect::<StdResult<_>>()?;
+    Ok(task_ids)
+}
+
+/// Check no auto-renewal task renews `domain` yet, a second one would pay for it twice
+fn assert_no_renewal_task(storage: &dyn Storage, domain: &str) -> AppResult<()> {
+    if let Some(task_id) = renewal_tasks(storage, domain)?.first() {
+        return Err(AppError::DuplicateRenewalTask {
+            domain: domain.to_string(),
+            task_id: task_id.0,
+        });
+    }
+    Ok(())
+}
+
+/// Check every auto-renewal task still renews for an allowed period, CronCat drops a task whose
+/// renewal fails
+fn assert_tasks_within_max_years(storage: &dyn Storage, config: &Config) -> AppResult<()> {
+    for item in TASK_LIST.range(storage, None, None, Order::Ascending) {
+        let (task_id, task) = item?;
+        if task.renewal_years > config.max_registration_years {
+            return Err(AppError::TaskExceedsMaxYears {
+                task_id,
+                years: task.renewal_years,
+                max: config.max_registration_years,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Link the domain book entry of `domain_name` to `task_id`, if the domain is tracked
+fn set_renewal_task(
+    storage: &mut dyn Storage,
+    domain_name: &str,
+    task_id: Option<TaskId>,
+) -> AppResult<()> {
+    let domain = full_domain_name(domain_name);
+    if let Some(mut record) = domains().may_load(storage, &domain)? {
+        record.renewal_task = task_id;
+        domains().save(storage, &domain, &record)?;
+    }
+    Ok(())
+}
 
 
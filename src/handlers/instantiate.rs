@@ -1,11 +1,17 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
 use abstract_app::abstract_sdk::features::AbstractNameService;
+use abstract_app::objects::AssetEntry;
 use cw_asset::AssetInfoBase;
 
 use crate::contract::{App, AppResult};
 use crate::error::AppError;
+use crate::migrations::CURRENT_STATE_VERSION;
 use crate::msg::AppInstantiateMsg;
-use crate::state::{Config, CONFIG, COUNT};
+use crate::registry::{resolve_registry_addr, validate_years};
+use crate::state::{
+    Config, TaskId, CONFIG, DEFAULT_GAS_LIMIT, DEFAULT_MAX_REGISTRATION_YEARS,
+    DEFAULT_RENEWAL_YEARS, NEXT_ID, STATE_VERSION,
+};
 
 pub fn instantiate_handler(
     deps: DepsMut,
@@ -14,28 +20,52 @@ pub fn instantiate_handler(
     app: App,
     msg: AppInstantiateMsg,
 ) -> AppResult {
-    println!("Hey init");
-    let name_service = app.name_service(deps.as_ref());
-    let asset = name_service.query(&msg.native_asset)?;
-    let native_denom = match asset {
-        AssetInfoBase::Native(denom) => denom,
-        _ => return Err(AppError::NotNativeAsset {}),
-    };
+    let native_denom = resolve_native_denom(deps.as_ref(), &app, &msg.native_asset)?;
 
-    println!("Initiating...");
+    let registry_addr = resolve_registry_addr(deps.as_ref(), &app, msg.registry)?;
+    let registry_denom = resolve_native_denom(deps.as_ref(), &app, &msg.registry_asset)?;
 
     let config: Config = Config {
         native_denom,
+        registry_denom,
         task_creation_amount: msg.task_creation_amount,
         refill_threshold: msg.refill_threshold,
+        registry_addr,
+        default_gas_limit: DEFAULT_GAS_LIMIT,
+        default_renewal_years: DEFAULT_RENEWAL_YEARS,
+        max_registration_years: msg
+            .max_registration_years
+            .unwrap_or(DEFAULT_MAX_REGISTRATION_YEARS),
     };
-
-    println!("Initiating...");
+    validate_config(&config)?;
 
     CONFIG.save(deps.storage, &config)?;
-    COUNT.save(deps.storage, &msg.count)?;
-
-    println!("Initiating...");
+    NEXT_ID.save(deps.storage, &TaskId::default())?;
+    STATE_VERSION.save(deps.storage, &CURRENT_STATE_VERSION)?;
 
     Ok(Response::new())
 }
+
+/// Check the config values are usable and consistent with each other
+pub(crate) fn validate_config(config: &Config) -> AppResult<()> {
+    if config.default_gas_limit == 0 {
+        return Err(AppError::ZeroGasLimit {});
+    }
+    // A refill adds the creation amount, which must lift a drained task back over the threshold
+    if config.refill_threshold > config.task_creation_amount {
+        return Err(AppError::RefillThresholdTooHigh {
+            refill_threshold: config.refill_threshold,
+            task_creation_amount: config.task_creation_amount,
+        });
+    }
+    validate_years(config, config.default_renewal_years)
+}
+
+/// Resolve the denom of a native asset registered in the Abstract name service
+pub(crate) fn resolve_native_denom(deps: Deps, app: &App, asset: &AssetEntry) -> AppResult<String> {
+    let name_service = app.name_service(deps);
+    match name_service.query(asset)? {
+        AssetInfoBase::Native(denom) => Ok(denom),
+        _ => Err(AppError::NotNativeAsset {}),
+    }
+}
@@ -1,10 +1,35 @@
 use crate::contract::{App, AppResult};
+use crate::handlers::instantiate::resolve_native_denom;
+use crate::migrations::{self, MigrationContext, CURRENT_STATE_VERSION};
 use crate::msg::AppMigrateMsg;
+use crate::registry::resolve_registry_addr;
+use crate::state::STATE_VERSION;
+use abstract_app::abstract_sdk::features::AccountIdentification;
 use abstract_app::traits::AbstractResponse;
 use cosmwasm_std::{DepsMut, Env};
 
 /// Handle the app migrate msg
 /// The top-level Abstract app does version checking and dispatches to this handler
-pub fn migrate_handler(_deps: DepsMut, _env: Env, app: App, _msg: AppMigrateMsg) -> AppResult {
-    Ok(app.response("migrate"))
+pub fn migrate_handler(deps: DepsMut, env: Env, app: App, msg: AppMigrateMsg) -> AppResult {
+    // Instances without a state version predate versioned migrations
+    let from = STATE_VERSION.may_load(deps.storage)?.unwrap_or_default();
+
+    let ctx = MigrationContext {
+        block_time: env.block.time,
+        proxy: app.proxy_address(deps.as_ref())?,
+        registry_addr: msg
+            .registry
+            .map(|registry| resolve_registry_addr(deps.as_ref(), &app, registry))
+            .transpose()?,
+        registry_denom: msg
+            .registry_asset
+            .map(|asset| resolve_native_denom(deps.as_ref(), &app, &asset))
+            .transpose()?,
+    };
+    migrations::migrate(deps.storage, from, &ctx)?;
+
+    Ok(app
+        .response("migrate")
+        .add_attribute("from_state_version", from.to_string())
+        .add_attribute("to_state_version", CURRENT_STATE_VERSION.to_string()))
 }
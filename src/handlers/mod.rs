@@ -1,3 +1,4 @@
+mod authorization;
 pub mod execute;
 pub mod instantiate;
 pub mod migrate;
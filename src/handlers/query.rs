@@ -1,17 +1,53 @@
 use crate::contract::{App, AppResult};
-use crate::msg::{AppQueryMsg, ConfigResponse, CountResponse, DefaultIdResponse, NameResolutionResponse};
-use crate::state::{CONFIG, COUNT, DEFAULT_ID_MAP};
-use archid_registry::msg::QueryMsg::ResolveRecord;
-use archid_registry::msg::ResolveRecordResponse;
-use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env, QueryRequest, StdResult, WasmQuery};
-use abstract_app::abstract_sdk::features::AbstractNameService;
-
-pub fn query_handler(deps: Deps, _env: Env, app: &App, msg: AppQueryMsg) -> AppResult<Binary> {
+use crate::msg::{
+    AddressesForDefaultIdResponse, AppQueryMsg, ConfigResponse, DefaultIdResponse,
+    DefaultIdsResponse, DomainResponse, DomainsResponse, NameResolutionResponse,
+    QuoteRegistrationResponse, RolesResponse, TaskResponse, TasksResponse,
+};
+use crate::error::AppError;
+use crate::registry::{full_domain_name, query_record, query_registry_config, registration_cost};
+use crate::state::{default_ids, domains, TaskId, CONFIG, OPERATORS, SUBDOMAINS, TASK_LIST};
+use crate::validation::validate_record_name;
+use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn query_handler(deps: Deps, env: Env, _app: &App, msg: AppQueryMsg) -> AppResult<Binary> {
     match msg {
         AppQueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        AppQueryMsg::Count {} => to_json_binary(&query_count(deps)?),
         AppQueryMsg::NameResolution { domain_name } => to_json_binary(&query_name_resolution(deps, &domain_name)?),
-        AppQueryMsg::DefaultId { address } => to_json_binary(&query_default_id(deps, app, address)?),
+        AppQueryMsg::DefaultId { address } => to_json_binary(&query_default_id(deps, address)?),
+        AppQueryMsg::DefaultIds { addresses } => {
+            to_json_binary(&query_default_ids(deps, addresses)?)
+        }
+        AppQueryMsg::AddressesForDefaultId { name } => {
+            to_json_binary(&query_addresses_for_default_id(deps, &name)?)
+        }
+        AppQueryMsg::QuoteRegistration { name, years } => {
+            to_json_binary(&query_quote_registration(deps, &name, years)?)
+        }
+        AppQueryMsg::Domains { start_after, limit } => {
+            to_json_binary(&query_domains(deps, start_after, limit)?)
+        }
+        AppQueryMsg::Domain { name } => to_json_binary(&query_domain(deps, &name)?),
+        AppQueryMsg::Task { task_id } => to_json_binary(&query_task(deps, task_id)?),
+        AppQueryMsg::Tasks { start_after, limit } => {
+            to_json_binary(&query_tasks(deps, start_after, limit)?)
+        }
+        AppQueryMsg::ExpiringSoon {
+            within_seconds,
+            start_after,
+            limit,
+        } => to_json_binary(&query_expiring_soon(
+            deps,
+            env,
+            within_seconds,
+            start_after,
+            limit,
+        )?),
+        AppQueryMsg::Roles { address } => to_json_binary(&query_roles(deps, &address)?),
     }
     .map_err(Into::into)
 }
@@ -22,47 +58,141 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         native_asset: config.native_denom.into(),
         task_creation_amount: config.task_creation_amount.into(),
         refill_threshold: config.refill_threshold.into(),
+        registry_addr: config.registry_addr,
+        registry_asset: config.registry_denom.into(),
+        default_gas_limit: config.default_gas_limit,
+        default_renewal_years: config.default_renewal_years,
+        max_registration_years: config.max_registration_years,
     })
 }
 
-fn query_count(deps: Deps) -> StdResult<CountResponse> {
-    let count = COUNT.load(deps.storage)?;
-    Ok(CountResponse { count })
+fn query_name_resolution(deps: Deps, domain_name: &str) -> AppResult<NameResolutionResponse> {
+    let domain_name = validate_record_name(domain_name)?;
+    let registry_contract = CONFIG.load(deps.storage)?.registry_addr;
+    let query_resp = query_record(deps, &registry_contract, &domain_name)?;
+
+    Ok(NameResolutionResponse { query_resp })
+}
+
+fn query_quote_registration(deps: Deps, name: &str, years: u64) -> AppResult<QuoteRegistrationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let registry_config = query_registry_config(deps, &config.registry_addr)?;
+    let funds = registration_cost(&config, &registry_config, name, years)?;
+    Ok(QuoteRegistrationResponse { funds: vec![funds] })
 }
 
-fn query_name_resolution(deps: Deps, domain_name: &str) -> StdResult<NameResolutionResponse> {
+fn query_domains(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<DomainsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
 
-    let registry_contract = "archway1lr8rstt40s697hqpedv2nvt27f4cuccqwvly9gnvuszxmcevrlns60xw4r";
-    // Testnet: archway1lr8rstt40s697hqpedv2nvt27f4cuccqwvly9gnvuszxmcevrlns60xw4r
-    // Mainnet: archway1275jwjpktae4y4y0cdq274a2m0jnpekhttnfuljm6n59wnpyd62qppqxq0
+    let domains = domains()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<_>>()?;
 
-    // Create the query msg
-    let query_msg = ResolveRecord {
-        name: domain_name.to_string(),
-    };
+    Ok(DomainsResponse { domains })
+}
+
+fn query_domain(deps: Deps, name: &str) -> AppResult<DomainResponse> {
+    let name = full_domain_name(name);
+    let domain = domains()
+        .may_load(deps.storage, &name)?
+        .ok_or_else(|| AppError::DomainNotFound { domain: name.clone() })?;
+    let subdomains = SUBDOMAINS
+        .prefix(&name)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<_>>()?;
 
-    // Do the query request
-    let query_req = QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: registry_contract.into(),
-        msg: to_json_binary(&query_msg).unwrap(),
-    });
-    // Get the query result
-    let query_resp: ResolveRecordResponse = deps.querier.query(&query_req)?;
+    Ok(DomainResponse { domain, subdomains })
+}
 
-    Ok(NameResolutionResponse {query_resp})
+fn query_task(deps: Deps, task_id: TaskId) -> AppResult<TaskResponse> {
+    let task = TASK_LIST
+        .may_load(deps.storage, task_id)?
+        .ok_or(AppError::TaskNotFound { task_id: task_id.0 })?;
+    Ok(TaskResponse { task })
 }
 
-fn query_default_id(deps: Deps, app: &App, address: Addr) -> StdResult<DefaultIdResponse> {
+fn query_tasks(deps: Deps, start_after: Option<TaskId>, limit: Option<u32>) -> StdResult<TasksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
 
-    // TODO: Check if the address can be found in the MAP.
-    let default_id_map = DEFAULT_ID_MAP.load(deps.storage, address)?;
+    let tasks = TASK_LIST
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(id, task)| (TaskId(id), task)))
+        .collect::<StdResult<_>>()?;
 
-    // let ns = app.name_service(deps.clone());
-    // ns.query(entry)
-    
-    Ok(
-        DefaultIdResponse { 
-            default_id: default_id_map 
+    Ok(TasksResponse { tasks })
+}
+
+fn query_expiring_soon(
+    deps: Deps,
+    env: Env,
+    within_seconds: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> AppResult<DomainsResponse> {
+    // Compared in seconds so windows reaching past the largest timestamp cover every domain
+    let deadline = env.block.time.seconds().saturating_add(within_seconds);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // Continue after the last domain of the previous page, in (expiry, name) order
+    let start = match start_after {
+        Some(name) => {
+            let name = full_domain_name(&name);
+            let record = domains()
+                .may_load(deps.storage, &name)?
+                .ok_or_else(|| AppError::DomainNotFound { domain: name.clone() })?;
+            Some(Bound::exclusive((record.expiry.seconds(), name)))
         }
-    )
-}
\ No newline at end of file
+        None => None,
+    };
+
+    let domains = domains()
+        .idx
+        .expiry
+        .range(deps.storage, start, None, Order::Ascending)
+        .take_while(|item| item.as_ref().map_or(true, |(_, record)| record.expiry.seconds() <= deadline))
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<_>>()?;
+
+    Ok(DomainsResponse { domains })
+}
+
+fn query_default_id(deps: Deps, address: Addr) -> StdResult<DefaultIdResponse> {
+    let default_id = default_ids().may_load(deps.storage, address)?;
+    Ok(DefaultIdResponse { default_id })
+}
+
+fn query_default_ids(deps: Deps, addresses: Vec<String>) -> StdResult<DefaultIdsResponse> {
+    let default_ids = addresses
+        .into_iter()
+        .map(|address| {
+            let address = deps.api.addr_validate(&address)?;
+            let default_id = default_ids().may_load(deps.storage, address.clone())?;
+            Ok((address, default_id))
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(DefaultIdsResponse { default_ids })
+}
+
+fn query_roles(deps: Deps, address: &str) -> StdResult<RolesResponse> {
+    let address = deps.api.addr_validate(address)?;
+    let roles = OPERATORS.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(RolesResponse { roles })
+}
+
+fn query_addresses_for_default_id(deps: Deps, name: &str) -> StdResult<AddressesForDefaultIdResponse> {
+    let addresses = default_ids()
+        .idx
+        .name
+        .prefix(full_domain_name(name))
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+
+    Ok(AddressesForDefaultIdResponse { addresses })
+}
@@ -1,9 +1,13 @@
 pub mod contract;
 pub mod error;
+pub mod events;
 mod handlers;
+pub mod migrations;
 pub mod msg;
+pub mod registry;
 mod replies;
 pub mod state;
+pub mod validation;
 
 #[cfg(feature = "interface")]
 pub use contract::interface::AppInterface;
@@ -0,0 +1,129 @@
+//! Ordered upgrade steps of the app state.
+//!
+//! Abstract stores the new module version before it calls the migrate handler, so the app tracks
+//! the layout of its own state in [`STATE_VERSION`] and runs every step past the stored one.
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+
+use crate::contract::AppResult;
+use crate::error::AppError;
+use crate::state::{
+    Config, TaskEntry, TaskId, CONFIG, DEFAULT_GAS_LIMIT, DEFAULT_MAX_REGISTRATION_YEARS,
+    DEFAULT_RENEWAL_YEARS, NEXT_ID, STATE_VERSION, TASK_LIST,
+};
+
+/// Migrates the state from version `i` to `i + 1`
+pub type MigrationStep = fn(&mut dyn Storage, &MigrationContext) -> AppResult<()>;
+
+/// Upgrade steps in order, the step at index `i` migrates state version `i`
+const STEPS: &[MigrationStep] = &[v0_to_v1];
+
+/// State version written by this code
+pub const CURRENT_STATE_VERSION: u16 = STEPS.len() as u16;
+
+/// Values the upgrade steps can't derive from the stored state
+pub struct MigrationContext {
+    /// Block time of the migration
+    pub block_time: Timestamp,
+    /// Account proxy the app is installed on
+    pub proxy: Addr,
+    /// ArchID registry provided through the migrate message
+    pub registry_addr: Option<Addr>,
+    /// Denom the registry charges in, provided through the migrate message
+    pub registry_denom: Option<String>,
+}
+
+/// Run every upgrade step after `from` and record the new state version
+pub fn migrate(storage: &mut dyn Storage, from: u16, ctx: &MigrationContext) -> AppResult<()> {
+    for step in STEPS.iter().skip(from.into()) {
+        step(storage, ctx)?;
+    }
+    STATE_VERSION.save(storage, &CURRENT_STATE_VERSION)?;
+    Ok(())
+}
+
+/// Layout of the template-based app (0.0.2)
+mod v0 {
+    use cosmwasm_std::Uint128;
+
+    #[cosmwasm_schema::cw_serde]
+    pub struct Config {
+        pub native_denom: String,
+        pub task_creation_amount: Uint128,
+        pub refill_threshold: Uint128,
+    }
+
+    #[cosmwasm_schema::cw_serde]
+    pub struct TaskEntry {
+        pub frequency: String,
+        pub domain_name: String,
+    }
+}
+
+/// Add the registry, its denom, the registration period limit and task defaults to the config,
+/// extend task entries with their history, drop unverified default IDs and the template counter
+fn v0_to_v1(storage: &mut dyn Storage, ctx: &MigrationContext) -> AppResult<()> {
+    let registry_addr = ctx
+        .registry_addr
+        .clone()
+        .ok_or(AppError::MissingMigrationField {
+            field: "registry".to_owned(),
+        })?;
+
+    let config = Item::<v0::Config>::new("config").load(storage)?;
+    CONFIG.save(
+        storage,
+        &Config {
+            registry_denom: ctx
+                .registry_denom
+                .clone()
+                .unwrap_or_else(|| config.native_denom.clone()),
+            native_denom: config.native_denom,
+            task_creation_amount: config.task_creation_amount,
+            refill_threshold: config.refill_threshold,
+            registry_addr,
+            default_gas_limit: DEFAULT_GAS_LIMIT,
+            default_renewal_years: DEFAULT_RENEWAL_YEARS,
+            max_registration_years: DEFAULT_MAX_REGISTRATION_YEARS,
+        },
+    )?;
+
+    let tasks: Vec<_> = Map::<TaskId, v0::TaskEntry>::new("task_list")
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for (id, task) in tasks {
+        let task_entry = TaskEntry {
+            creator: ctx.proxy.clone(),
+            created_at: ctx.block_time,
+            frequency: task.frequency,
+            domain_name: task.domain_name,
+            renewal_years: DEFAULT_RENEWAL_YEARS,
+            // Legacy tasks renewed on every run
+            renew_before: Duration::Time(u64::MAX),
+            gas_limit: DEFAULT_GAS_LIMIT,
+            funding: Uint128::zero(),
+            last_run: None,
+            run_count: 0,
+            last_error: None,
+        };
+        TASK_LIST.save(storage, TaskId(id), &task_entry)?;
+    }
+
+    // Legacy default IDs were set without checking domain ownership
+    let legacy_default_ids = Map::<Addr, String>::new("default_id_map");
+    let addresses: Vec<_> = legacy_default_ids
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for address in addresses {
+        legacy_default_ids.remove(storage, address);
+    }
+
+    storage.remove(b"count");
+    if NEXT_ID.may_load(storage)?.is_none() {
+        NEXT_ID.save(storage, &TaskId::default())?;
+    }
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use abstract_app::objects::AssetEntry;
-use archid_registry::msg::ResolveRecordResponse;
+use abstract_app::objects::{AssetEntry, UncheckedContractEntry};
+use archid_registry::msg::{MetaDataUpdateMsg, ResolveRecordResponse};
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_utils::Duration;
 
-use crate::{contract::App, state::TaskId};
+use crate::{
+    contract::App,
+    state::{DomainRecord, Role, SubdomainRecord, TaskEntry, TaskId},
+};
 
 // This is used for type safety and re-exporting the contract endpoint structs.
 abstract_app::app_msg_types!(App, AppExecuteMsg, AppQueryMsg);
@@ -11,11 +15,25 @@ abstract_app::app_msg_types!(App, AppExecuteMsg, AppQueryMsg);
 /// App instantiate message
 #[cosmwasm_schema::cw_serde]
 pub struct AppInstantiateMsg {
-    /// Initial count
-    pub count: i32,
     pub native_asset: AssetEntry,
     pub task_creation_amount: Uint128,
     pub refill_threshold: Uint128,
+    /// ArchID registry contract the app registers and renews domains with
+    pub registry: RegistryAddress,
+    /// Native asset the registry charges registrations and renewals in
+    pub registry_asset: AssetEntry,
+    /// Longest registration or renewal period paid at once, defaults to
+    /// [`crate::state::DEFAULT_MAX_REGISTRATION_YEARS`]
+    pub max_registration_years: Option<u64>,
+}
+
+/// Location of the ArchID registry contract
+#[cosmwasm_schema::cw_serde]
+pub enum RegistryAddress {
+    /// Registry contract address
+    Addr(String),
+    /// Registry contract entry in the Abstract name service, e.g. `archid:registry`
+    Ans(UncheckedContractEntry),
 }
 
 /// App execute messages
@@ -23,29 +41,134 @@ pub struct AppInstantiateMsg {
 #[cfg_attr(feature = "interface", derive(cw_orch::ExecuteFns))]
 #[cfg_attr(feature = "interface", impl_into(ExecuteMsg))]
 pub enum AppExecuteMsg {
-    /// Increment count by 1
-    Increment {},
-    /// Admin method - reset count
-    Reset {
-        /// Count value after reset
-        count: i32,
-    },
-    UpdateConfig {},
-    UpdateDefaultID {},
-    #[cfg_attr(feature = "interface", payable)]
+    /// Admin method - update the fields that are set
+    UpdateConfig {
+        /// Native asset used to fund CronCat tasks
+        native_asset: Option<AssetEntry>,
+        task_creation_amount: Option<Uint128>,
+        refill_threshold: Option<Uint128>,
+        registry: Option<RegistryAddress>,
+        /// Native asset the registry charges registrations and renewals in
+        registry_asset: Option<AssetEntry>,
+        /// Gas limit of the renewal action of new auto-renewal tasks
+        default_gas_limit: Option<u64>,
+        /// Renewal period of new auto-renewal tasks that don't specify one
+        default_renewal_years: Option<u64>,
+        /// Longest registration or renewal period paid at once
+        max_registration_years: Option<u64>,
+    },
+    /// Grant an operator a scoped role
+    GrantRole {
+        address: String,
+        role: Role,
+    },
+    /// Revoke a role from an operator
+    RevokeRole {
+        address: String,
+        role: Role,
+    },
+    /// Set the sender's default ID to a domain owned by the sender, or owned by the account if the
+    /// sender is the admin or an operator with [`Role::SetDefaultId`]
+    UpdateDefaultID {
+        name: String,
+    },
+    /// Remove the sender's default ID
+    ClearDefaultID {},
+    /// Register a domain paid for by the account proxy
     RegisterDomain {
-        desired_name: String,
+        /// Name to register, without the `.arch` suffix
+        #[serde(alias = "desired_name")]
+        name: String,
+        /// Registration period in years, defaults to one year
+        #[serde(default = "default_registration_years")]
+        years: u64,
+        /// Owner of the domain NFT, defaults to the account proxy.
+        /// Only the account admin may register to another owner
+        owner: Option<String>,
     },
+    /// Deprecated, use [`AppExecuteMsg::RegisterDomain`]. Registers `desired_name` for one year,
+    /// owned by the account proxy
     RegisterDomain2 {
         desired_name: String,
     },
+    /// Create a CronCat task renewing a domain, a domain can only have one
     CreateAutoRenewalTask {
         frequency: String,
         domain_name: String,
+        /// Number of years the domain is extended by on every run,
+        /// defaults to the configured renewal period
+        renewal_years: Option<u64>,
+        /// Only renew once the domain expires within this window, must be a time duration
+        renew_before: Duration,
     },
     RenewDomain {
         task_id: TaskId
-    }
+    },
+    /// Register several domains owned by the account proxy in a single account action
+    RegisterDomains {
+        /// Domain names with their registration period in years
+        names: Vec<(String, u64)>,
+    },
+    /// Renew several domains in a single account action
+    RenewDomains {
+        /// Domain names with their renewal period in years
+        names: Vec<(String, u64)>,
+    },
+    /// Create a subdomain of a domain held by the account
+    RegisterSubdomain {
+        /// Parent domain
+        domain: String,
+        /// Subdomain label, without the parent domain
+        subdomain: String,
+        new_resolver: String,
+        /// Owner of the subdomain, only the account admin may pick another owner than the proxy
+        new_owner: String,
+        /// Expiry of the subdomain in seconds, can't exceed the parent's expiry
+        expiration: u64,
+    },
+    /// Remove a subdomain of a domain held by the account
+    RemoveSubdomain {
+        domain: String,
+        subdomain: String,
+    },
+    /// Change the address a domain resolves to, defaults to the account proxy
+    UpdateResolver {
+        name: String,
+        new_resolver: Option<String>,
+    },
+    /// Edit the ArchID metadata (description, image, accounts, websites) of a domain
+    UpdateMetadata {
+        name: String,
+        metadata: MetaDataUpdateMsg,
+    },
+    /// Transfer a domain NFT out of the account, cancelling every renewal task of the domain and
+    /// clearing default IDs pointing at it
+    TransferDomain {
+        name: String,
+        recipient: String,
+    },
+    /// Add a domain already owned by the account proxy to the domain book
+    ImportDomain {
+        name: String,
+    },
+    /// Remove an auto-renewal task, refunding its remaining balance to the account proxy
+    CancelAutoRenewalTask {
+        task_id: TaskId,
+    },
+    /// Recreate an auto-renewal task under the same id with the given changes,
+    /// carrying its remaining balance over
+    UpdateAutoRenewalTask {
+        task_id: TaskId,
+        frequency: Option<String>,
+        renewal_years: Option<u64>,
+        gas_limit: Option<u64>,
+        domain_name: Option<String>,
+    },
+}
+
+/// Registration period of [`AppExecuteMsg::RegisterDomain`] messages sent without one
+fn default_registration_years() -> u64 {
+    1
 }
 
 /// App query messages
@@ -56,8 +179,6 @@ pub enum AppExecuteMsg {
 pub enum AppQueryMsg {
     #[returns(ConfigResponse)]
     Config {},
-    #[returns(CountResponse)]
-    Count {},
     #[returns(NameResolutionResponse)]
     NameResolution {
         domain_name: String,
@@ -66,26 +187,87 @@ pub enum AppQueryMsg {
     DefaultId {
         address: Addr,
     },
+    /// Default IDs of several addresses at once
+    #[returns(DefaultIdsResponse)]
+    DefaultIds {
+        addresses: Vec<String>,
+    },
+    /// Addresses that use `name` as their default ID
+    #[returns(AddressesForDefaultIdResponse)]
+    AddressesForDefaultId {
+        name: String,
+    },
+    /// Funds the app attaches when registering `name` for `years`
+    #[returns(QuoteRegistrationResponse)]
+    QuoteRegistration {
+        name: String,
+        years: u64,
+    },
+    /// Domains registered through the app
+    #[returns(DomainsResponse)]
+    Domains {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(DomainResponse)]
+    Domain {
+        name: String,
+    },
+    #[returns(TaskResponse)]
+    Task {
+        task_id: TaskId,
+    },
+    /// Auto-renewal tasks created by the app
+    #[returns(TasksResponse)]
+    Tasks {
+        start_after: Option<TaskId>,
+        limit: Option<u32>,
+    },
+    /// Domains expiring within `within_seconds` from now, soonest first
+    #[returns(DomainsResponse)]
+    ExpiringSoon {
+        within_seconds: u64,
+        /// Name of the last domain of the previous page
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Roles granted to an operator
+    #[returns(RolesResponse)]
+    Roles {
+        address: String,
+    },
 }
 
+/// App migrate message, carries the values new state versions require
 #[cosmwasm_schema::cw_serde]
-pub struct AppMigrateMsg {}
+pub struct AppMigrateMsg {
+    /// ArchID registry, required when migrating from a version without one
+    pub registry: Option<RegistryAddress>,
+    /// Asset the registry charges in, defaults to the native asset when migrating from a version
+    /// without one
+    pub registry_asset: Option<AssetEntry>,
+}
 
 #[cosmwasm_schema::cw_serde]
 pub struct ConfigResponse {
     /// Native gas/stake asset that used for attaching to croncat task
     pub native_asset: AssetEntry,
-    /// Initial amount in native asset that sent on creating/refilling DCA
+    /// Initial amount in native asset that sent on creating/refilling a renewal task
     /// to croncat to cover gas usage of agents
     pub task_creation_amount: Uint128,
     /// Threshold when task refill should happen
-    /// if it's lower during [`DCAExecuteMsg::Convert`] DCA will refill croncat task
-    pub refill_threshold: Uint128
-}
-
-#[cosmwasm_schema::cw_serde]
-pub struct CountResponse {
-    pub count: i32,
+    /// if it's lower during [`AppExecuteMsg::RenewDomain`] the app will refill the croncat task
+    pub refill_threshold: Uint128,
+    /// ArchID registry contract address
+    pub registry_addr: Addr,
+    /// Native asset the registry charges registrations and renewals in
+    pub registry_asset: AssetEntry,
+    /// Gas limit of the renewal action of new auto-renewal tasks
+    pub default_gas_limit: u64,
+    /// Renewal period of new auto-renewal tasks that don't specify one
+    pub default_renewal_years: u64,
+    /// Longest registration or renewal period paid at once
+    pub max_registration_years: u64,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -95,5 +277,46 @@ pub struct NameResolutionResponse {
 
 #[cosmwasm_schema::cw_serde]
 pub struct DefaultIdResponse {
-    pub default_id: String,
-}
\ No newline at end of file
+    pub default_id: Option<String>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DefaultIdsResponse {
+    pub default_ids: Vec<(Addr, Option<String>)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct AddressesForDefaultIdResponse {
+    pub addresses: Vec<Addr>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct QuoteRegistrationResponse {
+    pub funds: Vec<Coin>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DomainsResponse {
+    pub domains: Vec<DomainRecord>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DomainResponse {
+    pub domain: DomainRecord,
+    pub subdomains: Vec<SubdomainRecord>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TaskResponse {
+    pub task: TaskEntry,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TasksResponse {
+    pub tasks: Vec<(TaskId, TaskEntry)>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct RolesResponse {
+    pub roles: Vec<Role>,
+}
@@ -0,0 +1,122 @@
+use abstract_app::abstract_sdk::features::AbstractNameService;
+use archid_registry::msg::{QueryMsg as RegistryQueryMsg, ResolveRecordResponse};
+use archid_registry::state::Config as RegistryConfig;
+use cosmwasm_std::{Addr, Coin, Deps, StdError, Timestamp};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+
+use crate::contract::{App, AppResult};
+use crate::error::AppError;
+use crate::msg::RegistryAddress;
+use crate::state::Config;
+use crate::validation::validate_domain_name;
+
+/// Top-level suffix of ArchID names
+pub const ARCH_SUFFIX: &str = ".arch";
+
+/// Resolve the ArchID registry address, either directly or through the Abstract name service
+pub fn resolve_registry_addr(deps: Deps, app: &App, registry: RegistryAddress) -> AppResult<Addr> {
+    let registry_addr = match registry {
+        RegistryAddress::Addr(addr) => deps.api.addr_validate(&addr)?,
+        RegistryAddress::Ans(entry) => app.name_service(deps).query(&entry.check())?,
+    };
+    Ok(registry_addr)
+}
+
+/// Query the live configuration of the registry
+pub fn query_registry_config(deps: Deps, registry_addr: &Addr) -> AppResult<RegistryConfig> {
+    deps.querier
+        .query_wasm_smart(registry_addr, &RegistryQueryMsg::Config {})
+        .map_err(|error| registry_error(registry_addr, error))
+}
+
+/// Query the registry for the current expiry of `domain`
+pub fn query_record_expiration(deps: Deps, registry_addr: &Addr, domain: &str) -> AppResult<Timestamp> {
+    let record = query_record(deps, registry_addr, domain)?;
+    Ok(Timestamp::from_seconds(record.expiration))
+}
+
+/// Resolve the registry record of `domain`
+pub fn query_record(deps: Deps, registry_addr: &Addr, domain: &str) -> AppResult<ResolveRecordResponse> {
+    deps.querier
+        .query_wasm_smart(
+            registry_addr,
+            &RegistryQueryMsg::ResolveRecord {
+                name: domain.to_string(),
+            },
+        )
+        .map_err(|error| registry_error(registry_addr, error))
+}
+
+/// Query the current owner of the domain NFT
+pub fn query_domain_owner(deps: Deps, cw721_addr: &Addr, domain: &str) -> AppResult<Addr> {
+    let owner: OwnerOfResponse = deps
+        .querier
+        .query_wasm_smart(
+            cw721_addr,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: domain.to_string(),
+                include_expired: None,
+            },
+        )
+        .map_err(|error| registry_error(cw721_addr, error))?;
+    Ok(deps.api.addr_validate(&owner.owner)?)
+}
+
+fn registry_error(registry: &Addr, error: StdError) -> AppError {
+    AppError::RegistryError {
+        registry: registry.clone(),
+        reason: error.to_string(),
+    }
+}
+
+/// Full ArchID domain name (`name.arch`) used by registry queries and as NFT token id
+pub fn full_domain_name(name: &str) -> String {
+    if name.ends_with(ARCH_SUFFIX) {
+        name.to_string()
+    } else {
+        format!("{name}{ARCH_SUFFIX}")
+    }
+}
+
+/// Domain label without the `.arch` suffix, as taken by registry execute messages
+pub fn bare_domain_name(name: &str) -> &str {
+    name.strip_suffix(ARCH_SUFFIX).unwrap_or(name)
+}
+
+/// Check that `years` is a registration period the app pays for
+pub fn validate_years(config: &Config, years: u64) -> AppResult<()> {
+    if years == 0 || years > config.max_registration_years {
+        return Err(AppError::InvalidRegistrationYears {
+            years,
+            max: config.max_registration_years,
+        });
+    }
+    Ok(())
+}
+
+/// Time a registration or renewal of `years` adds to a domain's expiry
+pub fn registration_period(registry_config: &RegistryConfig, years: u64) -> u64 {
+    registry_config.base_expiration.saturating_mul(years)
+}
+
+/// Funds the registry expects for registering or renewing `name` for `years`.
+///
+/// The registry charges `base_cost` per year whatever the length of the name. Registrations and
+/// renewals still check the expiry the registry grants against the paid period and fail if the
+/// registry charged for less, see [`crate::state::ExpiryRefresh`].
+pub fn registration_cost(
+    config: &Config,
+    registry_config: &RegistryConfig,
+    name: &str,
+    years: u64,
+) -> AppResult<Coin> {
+    validate_years(config, years)?;
+    validate_domain_name(name)?;
+
+    let amount = registry_config.base_cost.checked_mul(years.into())?;
+
+    Ok(Coin {
+        denom: config.registry_denom.clone(),
+        amount,
+    })
+}
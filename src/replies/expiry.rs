@@ -0,0 +1,76 @@
+use crate::contract::{App, AppResult};
+use crate::error::AppError;
+use crate::events::ArchIdEvent;
+use crate::registry::query_record_expiration;
+use crate::state::{
+    domains, ExpiryRefresh, CONFIG, PENDING_EXPIRY_REFRESH, PENDING_RENEWAL_TASK, TASK_LIST,
+};
+
+use abstract_app::traits::AbstractResponse;
+use cosmwasm_std::{DepsMut, Env, Reply, SubMsgResult};
+
+/// Store the registry expiry of the domains that were just registered or renewed
+pub fn refresh_expiry_reply(deps: DepsMut, _env: Env, app: App, reply: Reply) -> AppResult {
+    let refreshes = PENDING_EXPIRY_REFRESH.load(deps.storage)?;
+    PENDING_EXPIRY_REFRESH.remove(deps.storage);
+    let renewal_task = PENDING_RENEWAL_TASK.may_load(deps.storage)?;
+    PENDING_RENEWAL_TASK.remove(deps.storage);
+
+    let last_error = match reply.result {
+        SubMsgResult::Ok(_) => None,
+        SubMsgResult::Err(error) => Some(error),
+    };
+    if let Some(task_id) = renewal_task {
+        if let Some(mut task_entry) = TASK_LIST.may_load(deps.storage, task_id)? {
+            task_entry.last_error = last_error.clone();
+            TASK_LIST.save(deps.storage, task_id, &task_entry)?;
+        }
+    }
+
+    // Only renewals reply on failure
+    if let Some(error) = last_error {
+        let mut response = app
+            .response("renew_domain_failed")
+            .add_attribute("error", error);
+        for ExpiryRefresh { domain, .. } in refreshes {
+            response = response
+                .add_event(ArchIdEvent::new("renew_domain_failed").domain(&domain).into())
+                .add_attribute("domain", domain);
+        }
+        return Ok(response);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut response = app.response("refresh_expiry");
+    for ExpiryRefresh { domain, min_expiry } in refreshes {
+        let expiry = query_record_expiration(deps.as_ref(), &config.registry_addr, &domain)?;
+        // The price is derived from the registry config, fail rather than pay for a shorter period
+        if expiry < min_expiry {
+            return Err(AppError::RegistryError {
+                registry: config.registry_addr,
+                reason: format!(
+                    "{domain} expires at {} but the paid period lasts until {}",
+                    expiry.seconds(),
+                    min_expiry.seconds()
+                ),
+            });
+        }
+
+        if let Some(mut record) = domains().may_load(deps.storage, &domain)? {
+            record.expiry = expiry;
+            domains().save(deps.storage, &domain, &record)?;
+        }
+
+        response = response
+            .add_event(
+                ArchIdEvent::new("refresh_expiry")
+                    .domain(&domain)
+                    .expiry(expiry)
+                    .into(),
+            )
+            .add_attribute("domain", domain)
+            .add_attribute("expiry", expiry.seconds().to_string());
+    }
+
+    Ok(response)
+}
@@ -1,5 +1,8 @@
+mod expiry;
 mod instantiate;
 
+pub use expiry::refresh_expiry_reply;
 pub use instantiate::instantiate_reply;
 
 pub const INSTANTIATE_REPLY_ID: u64 = 1u64;
+pub const REFRESH_EXPIRY_REPLY_ID: u64 = 2u64;
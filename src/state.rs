@@ -1,23 +1,191 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, KeyDeserialize, Key, Map, PrimaryKey};
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, KeyDeserialize, Key, Map, MultiIndex, PrimaryKey,
+};
+use cw_utils::Duration;
 
 #[cosmwasm_schema::cw_serde]
 pub struct Config {
+    /// Denom of the native asset that funds CronCat tasks
     pub native_denom: String,
+    /// Denom the ArchID registry charges registrations and renewals in
+    pub registry_denom: String,
     pub task_creation_amount: Uint128,
     pub refill_threshold: Uint128,
+    /// Address of the ArchID registry contract
+    pub registry_addr: Addr,
+    /// Gas limit of the renewal action of new auto-renewal tasks
+    pub default_gas_limit: u64,
+    /// Renewal period of new auto-renewal tasks that don't specify one
+    pub default_renewal_years: u64,
+    /// Longest registration or renewal period paid in a single registry call
+    pub max_registration_years: u64,
 }
 
+/// Initial gas limit of the renewal action of new auto-renewal tasks
+pub const DEFAULT_GAS_LIMIT: u64 = 300_000;
+/// Initial renewal period of new auto-renewal tasks
+pub const DEFAULT_RENEWAL_YEARS: u64 = 1;
+/// Initial longest registration period. The registry config only holds `base_cost` and
+/// `base_expiration` and sets no limit, so the app caps payments itself; 3 years is the longest
+/// period the ArchID dapp sells.
+pub const DEFAULT_MAX_REGISTRATION_YEARS: u64 = 3;
+
+/// Layout version of the stored state, see [`crate::migrations`]
+pub const STATE_VERSION: Item<u16> = Item::new("state_version");
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const COUNT: Item<i32> = Item::new("count");
-pub const DEFAULT_ID_MAP: Map<Addr, String> = Map::new("default_id_map");
 pub const NEXT_ID: Item<TaskId> = Item::new("next_id");
 pub const TASK_LIST: Map<TaskId, TaskEntry> = Map::new("task_list");
+/// Domains whose expiry is refreshed once the pending registrations or renewals succeed
+pub const PENDING_EXPIRY_REFRESH: Item<Vec<ExpiryRefresh>> = Item::new("pending_expiry_refresh");
+/// Task of the pending renewal, if the pending refresh comes from a renewal
+pub const PENDING_RENEWAL_TASK: Item<TaskId> = Item::new("pending_renewal_task");
+
+/// Roles granted to operators acting on behalf of the account admin
+pub const OPERATORS: Map<&Addr, Vec<Role>> = Map::new("operators");
+
+/// Scoped permission an operator can be granted
+#[cosmwasm_schema::cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    /// Register domains and subdomains paid for by the account and owned by its proxy, and import
+    /// domains
+    Register,
+    /// Renew domains paid for by the account
+    Renew,
+    /// Create, update and cancel auto-renewal tasks
+    ManageTasks,
+    /// Set a default ID to a domain owned by the account
+    SetDefaultId,
+    /// Change what account domains resolve to
+    UpdateResolver,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let role = match self {
+            Role::Register => "register",
+            Role::Renew => "renew",
+            Role::ManageTasks => "manage_tasks",
+            Role::SetDefaultId => "set_default_id",
+            Role::UpdateResolver => "update_resolver",
+        };
+        f.write_str(role)
+    }
+}
+
+/// Pending refresh of a domain's expiry after a registration or renewal
+#[cosmwasm_schema::cw_serde]
+pub struct ExpiryRefresh {
+    /// Full domain name
+    pub domain: String,
+    /// Lowest expiry the paid registration period must grant, the refresh fails below it
+    pub min_expiry: Timestamp,
+}
+
+/// Subdomains created through the app, keyed by parent domain and subdomain label
+pub const SUBDOMAINS: Map<(&str, &str), SubdomainRecord> = Map::new("subdomains");
+
+#[cosmwasm_schema::cw_serde]
+pub struct SubdomainRecord {
+    /// Full subdomain name, e.g. `sub.name.arch`
+    pub name: String,
+    /// Owner of the subdomain NFT
+    pub owner: Addr,
+    /// Address the subdomain resolves to
+    pub resolver: Addr,
+    /// Time the subdomain expires
+    pub expiry: Timestamp,
+}
+
+pub struct DefaultIdIndexes<'a> {
+    /// Addresses by their default ID
+    pub name: MultiIndex<'a, String, String, Addr>,
+}
+
+impl<'a> IndexList<String> for DefaultIdIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<String>> + '_> {
+        let v: Vec<&dyn Index<String>> = vec![&self.name];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Default ID (full domain name) of each address
+pub fn default_ids<'a>() -> IndexedMap<'a, Addr, String, DefaultIdIndexes<'a>> {
+    let indexes = DefaultIdIndexes {
+        name: MultiIndex::new(
+            |_pk, name| name.clone(),
+            "default_id_map",
+            "default_id_map__name",
+        ),
+    };
+    IndexedMap::new("default_id_map", indexes)
+}
+
+pub struct DomainIndexes<'a> {
+    /// Domains by expiry in seconds
+    pub expiry: MultiIndex<'a, u64, DomainRecord, String>,
+}
+
+impl<'a> IndexList<DomainRecord> for DomainIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DomainRecord>> + '_> {
+        let v: Vec<&dyn Index<DomainRecord>> = vec![&self.expiry];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Domains registered through the app, keyed by full domain name
+pub fn domains<'a>() -> IndexedMap<'a, &'a str, DomainRecord, DomainIndexes<'a>> {
+    let indexes = DomainIndexes {
+        expiry: MultiIndex::new(
+            |_pk, record| record.expiry.seconds(),
+            "domains",
+            "domains__expiry",
+        ),
+    };
+    IndexedMap::new("domains", indexes)
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct DomainRecord {
+    /// Full domain name, e.g. `name.arch`
+    pub name: String,
+    /// Owner of the domain NFT
+    pub owner: Addr,
+    /// Token id of the domain NFT
+    pub token_id: String,
+    /// Block height the domain was registered at
+    pub registered_height: u64,
+    /// Block time the domain was registered at
+    pub registered_time: Timestamp,
+    /// Time the registration expires
+    pub expiry: Timestamp,
+    /// Auto-renewal task linked to the domain
+    pub renewal_task: Option<TaskId>,
+}
 
 #[cosmwasm_schema::cw_serde]
 pub struct TaskEntry {
+    /// Address that created the task
+    pub creator: Addr,
+    /// Block time the task was created at
+    pub created_at: Timestamp,
     pub frequency: String,
     pub domain_name: String,
+    /// Number of years the domain is extended by on every run
+    pub renewal_years: u64,
+    /// Only renew once the domain expires within this window
+    pub renew_before: Duration,
+    /// Gas limit of the renewal action
+    pub gas_limit: u64,
+    /// Native amount the CronCat task was funded with
+    pub funding: Uint128,
+    /// Block time of the last run
+    pub last_run: Option<Timestamp>,
+    /// Number of times CronCat ran the task
+    pub run_count: u64,
+    /// Error of the last failed renewal, cleared by a successful one
+    pub last_error: Option<String>,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -0,0 +1,67 @@
+//! ArchID naming rules, checked before any message reaches the registry.
+//!
+//! `Register` and `RenewRegistration` take the bare name while `ResolveRecord` and the domain
+//! NFT take the full `name.arch` form, so names are normalized here once and converted with
+//! [`bare_domain_name`](crate::registry::bare_domain_name) where the registry expects it.
+
+use crate::contract::AppResult;
+use crate::error::AppError;
+use crate::registry::{full_domain_name, ARCH_SUFFIX};
+
+/// Longest domain or subdomain label the registry accepts
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+/// Validate a domain name given with or without the `.arch` suffix, returning its full form
+pub fn validate_domain_name(name: &str) -> AppResult<String> {
+    let label = name.strip_suffix(ARCH_SUFFIX).unwrap_or(name);
+    validate_label(name, label)?;
+    Ok(full_domain_name(label))
+}
+
+/// Validate a domain or `subdomain.domain` name given with or without the `.arch` suffix,
+/// returning its full form
+pub fn validate_record_name(name: &str) -> AppResult<String> {
+    let bare = name.strip_suffix(ARCH_SUFFIX).unwrap_or(name);
+    match bare.split_once('.') {
+        Some((subdomain, domain)) => {
+            validate_label(name, subdomain)?;
+            let domain = validate_domain_name(domain)?;
+            Ok(format!("{subdomain}.{domain}"))
+        }
+        None => validate_domain_name(name),
+    }
+}
+
+/// Validate a subdomain label, given without its parent domain
+pub fn validate_subdomain_label(label: &str) -> AppResult<()> {
+    validate_label(label, label)
+}
+
+fn validate_label(name: &str, label: &str) -> AppResult<()> {
+    let invalid = |reason: &str| AppError::InvalidDomainName {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if label.is_empty() {
+        return Err(invalid("name is empty"));
+    }
+    if label.len() > MAX_LABEL_LENGTH {
+        return Err(invalid(&format!(
+            "name is longer than {MAX_LABEL_LENGTH} characters"
+        )));
+    }
+    if label.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(invalid("name must be lowercase"));
+    }
+    if let Some(c) = label
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-'))
+    {
+        return Err(invalid(&format!("illegal character '{c}'")));
+    }
+    if label.starts_with('-') || label.ends_with('-') {
+        return Err(invalid("name can't start or end with '-'"));
+    }
+    Ok(())
+}
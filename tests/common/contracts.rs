@@ -97,3 +97,21 @@ pub fn croncat_agents_contract() -> Box<dyn Contract<Empty>> {
     );
     Box::new(contract)
 }
+
+pub fn archid_registry_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        archid_registry::contract::execute,
+        archid_registry::contract::instantiate,
+        archid_registry::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn archid_token_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        archid_token::entry::execute,
+        archid_token::entry::instantiate,
+        archid_token::entry::query,
+    );
+    Box::new(contract)
+}
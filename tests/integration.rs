@@ -12,7 +12,10 @@ use abstract_app::abstract_interface::*;
 use abstract_app::abstract_sdk::AbstractSdkError;
 use abstract_client::{AbstractClient, Account, Application, Namespace};
 use common::contracts;
-use cosmwasm_std::{coin, coins, to_json_binary, Addr, Decimal, StdError, Uint128};
+use archid_registry::msg::MetaDataUpdateMsg;
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_binary, Addr, Decimal, StdError, Timestamp, Uint128,
+};
 use croncat_app::{
     contract::{CRONCAT_ID, CRONCAT_MODULE_VERSION},
     croncat_integration_utils::{AGENTS_NAME, MANAGER_NAME, TASKS_NAME},
@@ -27,13 +30,22 @@ use croncat_sdk_manager::msg::ManagerInstantiateMsg;
 use croncat_sdk_tasks::msg::TasksInstantiateMsg;
 use cw20::Cw20Coin;
 use cw_asset::AssetInfo;
+use cw_utils::Duration;
 // Use prelude to get all the necessary imports
-use cw_orch::mock::cw_multi_test::Executor;
+use cw_orch::mock::cw_multi_test::{AppResponse, Executor};
 use cw_orch::{anyhow, prelude::*};
 use app::{
     contract::{APP_ID, APP_VERSION},
-    msg::{AppInstantiateMsg, ConfigResponse},
-    state::{TaskEntry, TaskId},
+    error::AppError,
+    events::EVENT_TYPE,
+    msg::{
+        AddressesForDefaultIdResponse, AppExecuteMsg, AppInstantiateMsg, ConfigResponse,
+        DefaultIdResponse, DefaultIdsResponse, DomainResponse, DomainsResponse,
+        QuoteRegistrationResponse, RegistryAddress, RolesResponse, TaskResponse, TasksResponse,
+    },
+    state::{
+        DomainRecord, Role, SubdomainRecord, TaskEntry, TaskId, DEFAULT_MAX_REGISTRATION_YEARS,
+    },
     *,
 };
 use wyndex_bundle::{WynDex, EUR, USD, WYNDEX};
@@ -51,12 +63,17 @@ struct DeployedApps {
     arch_app: Application<MockBech32, AppInterface<MockBech32>>,
     cron_cat_app: Application<MockBech32, Croncat<MockBech32>>,
     wyndex: WynDex,
+    registry: Addr,
+    archid_token: Addr,
 }
 // consts for testing
 const AGENT: &str = "agent";
 const VERSION: &str = "1.0";
 const DENOM: &str = "abstr";
+const REGISTRY_DENOM: &str = "aarch";
 const PAUSE_ADMIN: &str = "cosmos338dwgj5wm2tuahvfjdldz5s8hmt7l5aznw8jz9s2mmgj5c52jqgfq000";
+const REGISTRY_BASE_COST: u128 = 1_000_000;
+const REGISTRY_BASE_EXPIRATION: u64 = 31_536_000;
 
 /// A low-level cw-orchestrator setup script
 /// "low-level" because cron-cat doesn't use cw-orch itself.
@@ -259,6 +276,61 @@ fn setup_croncat_contracts(
     ))
 }
 
+/// Instantiate the ArchID registry with the domain NFT it mints
+fn setup_archid_registry(mock: MockBech32) -> anyhow::Result<(Addr, Addr)> {
+    let sender = mock.sender();
+    let mut app = mock.app.borrow_mut();
+
+    let code_id = app.store_code(contracts::archid_registry_contract());
+    let mut registry_config = archid_registry::state::Config {
+        admin: sender.clone(),
+        wallet: sender.clone(),
+        // Replaced once the token exists, the token needs the registry as minter
+        cw721: sender.clone(),
+        base_cost: Uint128::new(REGISTRY_BASE_COST),
+        base_expiration: REGISTRY_BASE_EXPIRATION,
+    };
+    let registry_addr = app.instantiate_contract(
+        code_id,
+        sender.clone(),
+        &archid_registry::msg::InstantiateMsg {
+            admin: registry_config.admin.clone(),
+            wallet: registry_config.wallet.clone(),
+            cw721: registry_config.cw721.clone(),
+            base_cost: registry_config.base_cost,
+            base_expiration: registry_config.base_expiration,
+        },
+        &[],
+        "archid-registry",
+        None,
+    )?;
+
+    let code_id = app.store_code(contracts::archid_token_contract());
+    let token_addr = app.instantiate_contract(
+        code_id,
+        sender.clone(),
+        &archid_token::InstantiateMsg {
+            name: "ArchID Token".to_owned(),
+            symbol: "AID".to_owned(),
+            minter: registry_addr.to_string(),
+        },
+        &[],
+        "archid-token",
+        None,
+    )?;
+
+    registry_config.cw721 = token_addr.clone();
+    app.execute_contract(
+        sender,
+        registry_addr.clone(),
+        &archid_registry::msg::ExecuteMsg::UpdateConfig {
+            config: registry_config,
+        },
+        &[],
+    )?;
+    Ok((registry_addr, token_addr))
+}
+
 /// Set up the test environment with the contract installed
 #[allow(clippy::type_complexity)]
 fn setup() -> anyhow::Result<(
@@ -277,6 +349,7 @@ fn setup() -> anyhow::Result<(
     mock.add_balance(&mock.addr_make(AGENT), coins(6_000_000_000, DENOM))?;
 
     let (cron_cat_addrs, _) = setup_croncat_contracts(mock.clone(), sender.to_string())?;
+    let (registry, archid_token) = setup_archid_registry(mock.clone())?;
 
     // Construct the DCA interface
 
@@ -285,7 +358,10 @@ fn setup() -> anyhow::Result<(
 
     // Deploy Abstract to the mock with the client
     let abstract_client = AbstractClient::builder(mock.clone())
-        .assets(vec![("denom".to_owned(), AssetInfo::native(DENOM).into())])
+        .assets(vec![
+            ("denom".to_owned(), AssetInfo::native(DENOM).into()),
+            ("arch".to_owned(), AssetInfo::native(REGISTRY_DENOM).into()),
+        ])
         .contract(
             UncheckedContractEntry::try_from(CRON_CAT_FACTORY)?,
             cron_cat_addrs.factory.to_string(),
@@ -316,10 +392,12 @@ fn setup() -> anyhow::Result<(
         // Note: Dex adapter and croncat app is a dependency of the DCA
         .install_app_with_dependencies::<AppInterface<MockBech32>>(
             &AppInstantiateMsg {
-                count: 0,
                 native_asset: AssetEntry::new("denom"),
                 task_creation_amount: Uint128::new(5_000_000),
-                refill_threshold: Uint128::new(1_00_000),
+                refill_threshold: Uint128::new(1_000_000),
+                registry: RegistryAddress::Addr(registry.to_string()),
+                registry_asset: AssetEntry::new("arch"),
+                max_registration_years: None,
             },
             Empty {},
         )?
@@ -328,7 +406,11 @@ fn setup() -> anyhow::Result<(
 
     mock.set_balance(
         &account.proxy()?,
-        vec![coin(50_000_000, DENOM), coin(10_000, EUR)],
+        vec![
+            coin(50_000_000, DENOM),
+            coin(50_000_000, REGISTRY_DENOM),
+            coin(10_000, EUR),
+        ],
     )?;
 
     let cron_cat_app = account.application::<Croncat<MockBech32>>()?;
@@ -336,6 +418,8 @@ fn setup() -> anyhow::Result<(
         arch_app,
         cron_cat_app,
         wyndex,
+        registry,
+        archid_token,
     };
     Ok((
         mock,
@@ -355,6 +439,65 @@ fn assert_querrier_err_eq<E: std::fmt::Display>(left: CwOrchError, right: E) {
     assert_eq!(left.root().to_string(), querier_contract_err().to_string())
 }
 
+/// Current owner of a domain NFT
+fn nft_owner(mock: &MockBech32, archid_token: &Addr, domain: &str) -> anyhow::Result<Addr> {
+    let owner: cw721::OwnerOfResponse = mock.app.borrow().wrap().query_wasm_smart(
+        archid_token,
+        &cw721::Cw721QueryMsg::OwnerOf {
+            token_id: domain.to_owned(),
+            include_expired: None,
+        },
+    )?;
+    Ok(Addr::unchecked(owner.owner))
+}
+
+/// Native balance CronCat holds for an auto-renewal task of the app
+fn task_balance(apps: &DeployedApps, task_id: TaskId) -> anyhow::Result<Uint128> {
+    let response = apps
+        .cron_cat_app
+        .task_balance(apps.arch_app.addr_str()?, task_id.into())?;
+    Ok(response
+        .balance
+        .map(|balance| balance.native_balance)
+        .unwrap_or_default())
+}
+
+/// Attributes of the `wasm-archid-auto` event the app emitted for `action`
+fn archid_auto_event(response: &AppResponse, action: &str) -> Vec<(String, String)> {
+    let event = response
+        .events
+        .iter()
+        .filter(|event| event.ty == format!("wasm-{EVENT_TYPE}"))
+        .find(|event| {
+            event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == action)
+        })
+        .unwrap_or_else(|| panic!("archid-auto event {action} missing"));
+    event
+        .attributes
+        .iter()
+        .filter(|attr| attr.key != "_contract_address")
+        .map(|attr| (attr.key.clone(), attr.value.clone()))
+        .collect()
+}
+
+/// Full `wasm-archid-auto` attribute list of `action`, keys not in `values` are empty
+fn expected_event(action: &str, values: &[(&str, &str)]) -> Vec<(String, String)> {
+    let value = |key: &str| {
+        values
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map_or("", |(_, value)| *value)
+    };
+    let mut attributes = vec![("action".to_owned(), action.to_owned())];
+    for key in ["domain", "years", "funds", "task_id", "expiry", "owner"] {
+        attributes.push((key.to_owned(), value(key).to_owned()));
+    }
+    attributes
+}
+
 #[test]
 fn successful_install() -> anyhow::Result<()> {
     // Set up the environment and contract
@@ -367,6 +510,11 @@ fn successful_install() -> anyhow::Result<()> {
             native_asset: AssetEntry::from("abstr"),
             task_creation_amount: Uint128::new(5_000_000),
             refill_threshold: Uint128::new(1_000_000),
+            registry_addr: apps.registry.clone(),
+            registry_asset: AssetEntry::from(REGISTRY_DENOM),
+            default_gas_limit: 300_000,
+            default_renewal_years: 1,
+            max_registration_years: DEFAULT_MAX_REGISTRATION_YEARS,
         }
     );
 
@@ -388,45 +536,1423 @@ fn successful_install() -> anyhow::Result<()> {
     Ok(())
 }
 
-// #[test]
-// fn successful_install() -> anyhow::Result<()> {
-//     let (_, app) = setup(0)?;
-
-//     let config = app.config()?;
-//     assert_eq!(config, ConfigResponse {});
-//     Ok(())
-// }
-
-// #[test]
-// fn successful_increment() -> anyhow::Result<()> {
-//     let (_, app) = setup(0)?;
-
-//     app.increment()?;
-//     let count: CountResponse = app.count()?;
-//     assert_eq!(count.count, 1);
-//     Ok(())
-// }
-
-// #[test]
-// fn successful_reset() -> anyhow::Result<()> {
-//     let (_, app) = setup(0)?;
-
-//     app.reset(42)?;
-//     let count: CountResponse = app.count()?;
-//     assert_eq!(count.count, 42);
-//     Ok(())
-// }
-
-// #[test]
-// fn failed_reset() -> anyhow::Result<()> {
-//     let (_, app) = setup(0)?;
-
-//     let err: AppError = app
-//         .call_as(&Addr::unchecked("NotAdmin"))
-//         .reset(9)
-//         .unwrap_err()
-//         .downcast()
-//         .unwrap();
-//     assert_eq!(err, AppError::Admin(AdminError::NotAdmin {}));
-//     Ok(())
-// }
+#[test]
+fn update_config() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    apps.arch_app.update_config(
+        Some(500_000),
+        Some(2),
+        Some(5),
+        None,
+        Some(Uint128::new(2_000_000)),
+        None,
+        None,
+        None,
+    )?;
+
+    let config: ConfigResponse = apps.arch_app.config()?;
+    assert_eq!(
+        config,
+        ConfigResponse {
+            native_asset: AssetEntry::from("abstr"),
+            task_creation_amount: Uint128::new(5_000_000),
+            refill_threshold: Uint128::new(2_000_000),
+            registry_addr: apps.registry.clone(),
+            registry_asset: AssetEntry::from(REGISTRY_DENOM),
+            default_gas_limit: 500_000,
+            default_renewal_years: 2,
+            max_registration_years: 5,
+        }
+    );
+
+    let err = apps
+        .arch_app
+        .update_config(Some(0), None, None, None, None, None, None, None)
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::ZeroGasLimit {}.to_string()));
+
+    let err = apps
+        .arch_app
+        .update_config(
+            None,
+            None,
+            None,
+            None,
+            Some(Uint128::new(6_000_000)),
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::RefillThresholdTooHigh {
+            refill_threshold: Uint128::new(6_000_000),
+            task_creation_amount: Uint128::new(5_000_000),
+        }
+        .to_string()
+    ));
+
+    // Tasks renewing for longer than the new maximum would fail on every run
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        Some(4),
+    )?;
+    let err = apps
+        .arch_app
+        .update_config(None, None, Some(3), None, None, None, None, None)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::TaskExceedsMaxYears {
+            task_id: 1,
+            years: 4,
+            max: 3,
+        }
+        .to_string()
+    ));
+    Ok(())
+}
+
+#[test]
+fn quote_registration_follows_registry_pricing() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    let quote: QuoteRegistrationResponse =
+        apps.arch_app.quote_registration("archid".to_owned(), 2)?;
+    assert_eq!(quote.funds, coins(2 * REGISTRY_BASE_COST, REGISTRY_DENOM));
+
+    // The quote reads the registry config on every call
+    let registry_config: archid_registry::state::Config = mock
+        .app
+        .borrow()
+        .wrap()
+        .query_wasm_smart(&apps.registry, &archid_registry::msg::QueryMsg::Config {})?;
+    mock.app.borrow_mut().execute_contract(
+        mock.sender(),
+        apps.registry.clone(),
+        &archid_registry::msg::ExecuteMsg::UpdateConfig {
+            config: archid_registry::state::Config {
+                base_cost: Uint128::new(3 * REGISTRY_BASE_COST),
+                ..registry_config
+            },
+        },
+        &[],
+    )?;
+    let quote: QuoteRegistrationResponse =
+        apps.arch_app.quote_registration("abc.arch".to_owned(), 2)?;
+    assert_eq!(quote.funds, coins(6 * REGISTRY_BASE_COST, REGISTRY_DENOM));
+
+    // Periods beyond the configured maximum are rejected
+    let err = apps
+        .arch_app
+        .quote_registration("archid".to_owned(), DEFAULT_MAX_REGISTRATION_YEARS + 1)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::InvalidRegistrationYears {
+            years: DEFAULT_MAX_REGISTRATION_YEARS + 1,
+            max: DEFAULT_MAX_REGISTRATION_YEARS,
+        }
+        .to_string()
+    ));
+    Ok(())
+}
+
+#[test]
+fn registrations_are_charged_the_quote_for_every_name_length() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+
+    // Short names are priced like long ones by the registry in the fixture
+    for (name, years) in [("abc", 2), ("xy", 1), ("a-much-longer-archid-name", 2)] {
+        let QuoteRegistrationResponse { funds } =
+            apps.arch_app.quote_registration(name.to_owned(), years)?;
+        let before = mock.query_balance(&proxy, REGISTRY_DENOM)?;
+
+        apps.arch_app.register_domain(name.to_owned(), None, years)?;
+        let after = mock.query_balance(&proxy, REGISTRY_DENOM)?;
+        assert_eq!(funds, vec![coin((before - after).u128(), REGISTRY_DENOM)]);
+
+        // The registry granted the whole paid period
+        let DomainResponse { domain, .. } = apps.arch_app.domain(name.to_owned())?;
+        let expiry = mock
+            .block_info()?
+            .time
+            .plus_seconds(years * REGISTRY_BASE_EXPIRATION);
+        assert_eq!(domain.expiry, expiry);
+    }
+    Ok(())
+}
+
+#[test]
+fn create_auto_renewal_task_persists_task() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    apps.arch_app.create_auto_renewal_task(
+        "archid.arch".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+
+    let TaskResponse { task } = apps.arch_app.task(TaskId(1))?;
+    assert_eq!(
+        task,
+        TaskEntry {
+            creator: mock.sender(),
+            created_at: mock.block_info()?.time,
+            frequency: "0 0 * * * *".to_owned(),
+            domain_name: "archid.arch".to_owned(),
+            renewal_years: 1,
+            renew_before: Duration::Time(86_400),
+            gas_limit: 300_000,
+            funding: Uint128::new(5_000_000),
+            last_run: None,
+            run_count: 0,
+            last_error: None,
+        }
+    );
+
+    let TasksResponse { tasks } = apps.arch_app.tasks(None, None)?;
+    assert_eq!(tasks, vec![(TaskId(1), task)]);
+    Ok(())
+}
+
+#[test]
+fn unset_default_id_is_none() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+    let sender = mock.sender();
+
+    let DefaultIdResponse { default_id } = apps.arch_app.default_id(sender.clone())?;
+    assert_eq!(default_id, None);
+
+    let DefaultIdsResponse { default_ids } =
+        apps.arch_app.default_ids(vec![sender.to_string()])?;
+    assert_eq!(default_ids, vec![(sender, None)]);
+
+    let AddressesForDefaultIdResponse { addresses } =
+        apps.arch_app.addresses_for_default_id("archid".to_owned())?;
+    assert!(addresses.is_empty());
+    Ok(())
+}
+
+#[test]
+fn default_ids_require_domain_ownership() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+    let holder = mock.addr_make("holder");
+    let stranger = mock.addr_make("stranger");
+    let operator = mock.addr_make("operator");
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    apps.arch_app
+        .register_domain("wallet".to_owned(), Some(holder.to_string()), 1)?;
+
+    // Domains held by someone else can't be claimed
+    let err = apps
+        .arch_app
+        .call_as(&stranger)
+        .update_default_id("wallet".to_owned())
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::NotDomainOwner {
+            domain: "wallet.arch".to_owned(),
+            sender: stranger.clone(),
+        }
+        .to_string()
+    ));
+
+    // Account domains are only handed out by the admin and its operators
+    let err = apps
+        .arch_app
+        .call_as(&stranger)
+        .update_default_id("archid".to_owned())
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::Unauthorized {
+            sender: stranger.clone(),
+            action: "update_default_id".to_owned(),
+        }
+        .to_string()
+    ));
+    let DefaultIdResponse { default_id } = apps.arch_app.default_id(stranger)?;
+    assert_eq!(default_id, None);
+
+    apps.arch_app
+        .call_as(&holder)
+        .update_default_id("wallet".to_owned())?;
+    let DefaultIdResponse { default_id } = apps.arch_app.default_id(holder)?;
+    assert_eq!(default_id, Some("wallet.arch".to_owned()));
+
+    apps.arch_app
+        .grant_role(operator.to_string(), Role::SetDefaultId)?;
+    apps.arch_app
+        .call_as(&operator)
+        .update_default_id("archid".to_owned())?;
+    let DefaultIdResponse { default_id } = apps.arch_app.default_id(operator)?;
+    assert_eq!(default_id, Some("archid.arch".to_owned()));
+    Ok(())
+}
+
+#[test]
+fn register_domains_checks_proxy_balance() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    mock.set_balance(&account.proxy()?, coins(REGISTRY_BASE_COST, REGISTRY_DENOM))?;
+
+    let err = apps
+        .arch_app
+        .register_domains(vec![
+            ("archid".to_owned(), 1),
+            ("abstract".to_owned(), 1),
+        ])
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::InsufficientFunds {
+            needed: coin(2 * REGISTRY_BASE_COST, REGISTRY_DENOM),
+            available: coin(REGISTRY_BASE_COST, REGISTRY_DENOM),
+        }
+        .to_string()
+    ));
+    Ok(())
+}
+
+#[test]
+fn batches_refresh_the_expiry_of_every_domain() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+    let expiry = |name: &str| -> anyhow::Result<Timestamp> {
+        let DomainResponse { domain, .. } = apps.arch_app.domain(name.to_owned())?;
+        let resolution = apps.arch_app.name_resolution(domain.name.clone())?;
+        assert_eq!(domain.expiry.seconds(), resolution.query_resp.expiration);
+        Ok(domain.expiry)
+    };
+
+    apps.arch_app.register_domains(vec![
+        ("archid".to_owned(), 1),
+        ("abstract".to_owned(), 2),
+    ])?;
+    let registered = mock.block_info()?.time;
+    assert_eq!(
+        expiry("archid")?,
+        registered.plus_seconds(REGISTRY_BASE_EXPIRATION)
+    );
+    assert_eq!(
+        expiry("abstract")?,
+        registered.plus_seconds(2 * REGISTRY_BASE_EXPIRATION)
+    );
+
+    apps.arch_app.renew_domains(vec![
+        ("archid".to_owned(), 2),
+        ("abstract".to_owned(), 1),
+    ])?;
+    assert_eq!(
+        expiry("archid")?,
+        registered.plus_seconds(3 * REGISTRY_BASE_EXPIRATION)
+    );
+    assert_eq!(
+        expiry("abstract")?,
+        registered.plus_seconds(3 * REGISTRY_BASE_EXPIRATION)
+    );
+
+    let err = apps.arch_app.register_domains(vec![]).unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::EmptyDomainList {}.to_string()));
+    let err = apps.arch_app.renew_domains(vec![]).unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::EmptyDomainList {}.to_string()));
+    Ok(())
+}
+
+#[test]
+fn registered_domains_are_kept_in_the_domain_book() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    let block = mock.block_info()?;
+    assert_eq!(nft_owner(&mock, &apps.archid_token, "archid.arch")?, proxy);
+
+    let record = DomainRecord {
+        name: "archid.arch".to_owned(),
+        owner: proxy.clone(),
+        token_id: "archid.arch".to_owned(),
+        registered_height: block.height,
+        registered_time: block.time,
+        expiry: block.time.plus_seconds(REGISTRY_BASE_EXPIRATION),
+        renewal_task: None,
+    };
+    let DomainResponse { domain, subdomains } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(domain, record);
+    assert!(subdomains.is_empty());
+
+    // The registration was paid from the proxy in the registry denom
+    let balance = mock.query_balance(&proxy, REGISTRY_DENOM)?;
+    assert_eq!(balance, Uint128::new(50_000_000 - REGISTRY_BASE_COST));
+
+    apps.arch_app.register_domain("abstract".to_owned(), None, 1)?;
+    let DomainsResponse { domains } = apps.arch_app.domains(None, None)?;
+    let names: Vec<_> = domains.iter().map(|domain| domain.name.as_str()).collect();
+    assert_eq!(names, vec!["abstract.arch", "archid.arch"]);
+
+    let DomainsResponse { domains } =
+        apps.arch_app.domains(Some("abstract.arch".to_owned()), Some(1))?;
+    assert_eq!(domains, vec![record]);
+    Ok(())
+}
+
+#[test]
+fn legacy_register_domain_messages_are_accepted() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+
+    // The pre-unification shape, without a period or an owner
+    let msg: AppExecuteMsg = from_json(r#"{"register_domain":{"desired_name":"archid"}}"#)?;
+    assert_eq!(
+        msg,
+        AppExecuteMsg::RegisterDomain {
+            name: "archid".to_owned(),
+            years: 1,
+            owner: None,
+        }
+    );
+    apps.arch_app.execute(&msg.into(), None)?;
+
+    let msg: AppExecuteMsg = from_json(r#"{"register_domain2":{"desired_name":"abstract"}}"#)?;
+    apps.arch_app.execute(&msg.into(), None)?;
+
+    let expiry = mock
+        .block_info()?
+        .time
+        .plus_seconds(REGISTRY_BASE_EXPIRATION);
+    for name in ["archid", "abstract"] {
+        let DomainResponse { domain, .. } = apps.arch_app.domain(name.to_owned())?;
+        assert_eq!(domain.owner, proxy);
+        assert_eq!(domain.expiry, expiry);
+    }
+    let balance = mock.query_balance(&proxy, REGISTRY_DENOM)?;
+    assert_eq!(balance, Uint128::new(50_000_000 - 2 * REGISTRY_BASE_COST));
+    Ok(())
+}
+
+#[test]
+fn registration_reply_stores_the_registry_expiry() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    let response = apps.arch_app.register_domain("archid".to_owned(), None, 2)?;
+    let expiry = mock
+        .block_info()?
+        .time
+        .plus_seconds(2 * REGISTRY_BASE_EXPIRATION);
+    assert!(response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "refresh_expiry")));
+
+    let resolution = apps.arch_app.name_resolution("archid.arch".to_owned())?;
+    assert_eq!(resolution.query_resp.expiration, expiry.seconds());
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(domain.expiry, expiry);
+    Ok(())
+}
+
+#[test]
+fn expiring_soon_pages_domains_by_expiry() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 3)?;
+    apps.arch_app.register_domain("abstract".to_owned(), None, 1)?;
+    apps.arch_app.register_domain("croncat".to_owned(), None, 2)?;
+    let names = |response: DomainsResponse| -> Vec<String> {
+        response.domains.into_iter().map(|domain| domain.name).collect()
+    };
+
+    let within = 2 * REGISTRY_BASE_EXPIRATION;
+    let expiring = apps.arch_app.expiring_soon(None, None, within)?;
+    assert_eq!(names(expiring), vec!["abstract.arch", "croncat.arch"]);
+
+    let page = apps.arch_app.expiring_soon(Some(1), None, within)?;
+    assert_eq!(names(page), vec!["abstract.arch"]);
+    let page = apps
+        .arch_app
+        .expiring_soon(Some(1), Some("abstract.arch".to_owned()), within)?;
+    assert_eq!(names(page), vec!["croncat.arch"]);
+    let page = apps
+        .arch_app
+        .expiring_soon(Some(1), Some("croncat.arch".to_owned()), within)?;
+    assert!(names(page).is_empty());
+
+    // Windows past the largest timestamp cover every domain
+    let expiring = apps.arch_app.expiring_soon(None, None, u64::MAX)?;
+    assert_eq!(
+        names(expiring),
+        vec!["abstract.arch", "croncat.arch", "archid.arch"]
+    );
+    Ok(())
+}
+
+#[test]
+fn renew_domain_waits_for_the_renewal_window() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, cron_cat_addrs) = setup()?;
+    let proxy = account.proxy()?;
+    let renew_before = 86_400;
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(renew_before),
+        None,
+    )?;
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    let registry_balance = mock.query_balance(&proxy, REGISTRY_DENOM)?;
+
+    // Outside the window the run is recorded but nothing is paid
+    let manager = apps.arch_app.call_as(&cron_cat_addrs.manager);
+    manager.renew_domain(TaskId(1))?;
+    let TaskResponse { task } = apps.arch_app.task(TaskId(1))?;
+    assert_eq!(task.run_count, 1);
+    assert_eq!(task.last_run, Some(mock.block_info()?.time));
+    let DomainResponse { domain: unchanged, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(unchanged.expiry, domain.expiry);
+    assert_eq!(mock.query_balance(&proxy, REGISTRY_DENOM)?, registry_balance);
+
+    // Inside the window the domain is renewed for the task's period
+    mock.wait_seconds(REGISTRY_BASE_EXPIRATION - renew_before)?;
+    manager.renew_domain(TaskId(1))?;
+    let TaskResponse { task } = apps.arch_app.task(TaskId(1))?;
+    assert_eq!(task.run_count, 2);
+    assert_eq!(task.last_error, None);
+    let DomainResponse { domain: renewed, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(
+        renewed.expiry,
+        domain.expiry.plus_seconds(REGISTRY_BASE_EXPIRATION)
+    );
+    assert_eq!(
+        mock.query_balance(&proxy, REGISTRY_DENOM)?,
+        registry_balance - Uint128::new(REGISTRY_BASE_COST)
+    );
+    Ok(())
+}
+
+#[test]
+fn update_auto_renewal_task_carries_the_task_balance_over() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let balance = task_balance(&apps, TaskId(1))?;
+    assert!(!balance.is_zero());
+
+    apps.arch_app.update_auto_renewal_task(
+        None,
+        Some("0 0 12 * * *".to_owned()),
+        Some(400_000),
+        Some(2),
+        TaskId(1),
+    )?;
+
+    let TaskResponse { task } = apps.arch_app.task(TaskId(1))?;
+    assert_eq!(task.frequency, "0 0 12 * * *");
+    assert_eq!(task.gas_limit, 400_000);
+    assert_eq!(task.renewal_years, 2);
+    assert_eq!(task.funding, balance);
+    assert_eq!(task_balance(&apps, TaskId(1))?, balance);
+
+    // A task without gas would fail on every run
+    let err = apps
+        .arch_app
+        .update_auto_renewal_task(None, None, Some(0), None, TaskId(1))
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::ZeroGasLimit {}.to_string()));
+    Ok(())
+}
+
+#[test]
+fn cancel_auto_renewal_task_refunds_the_proxy() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(domain.renewal_task, Some(TaskId(1)));
+    let balance = task_balance(&apps, TaskId(1))?;
+    assert!(!balance.is_zero());
+    let proxy_balance = mock.query_balance(&proxy, DENOM)?;
+
+    apps.arch_app.cancel_auto_renewal_task(TaskId(1))?;
+
+    // The remaining task balance went back to the proxy
+    assert_eq!(mock.query_balance(&proxy, DENOM)?, proxy_balance + balance);
+    let err = apps.arch_app.task(TaskId(1)).unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::TaskNotFound { task_id: 1 }.to_string()));
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(domain.renewal_task, None);
+    Ok(())
+}
+
+#[test]
+fn renew_domain_refills_low_task_balances() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, cron_cat_addrs) = setup()?;
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let balance = task_balance(&apps, TaskId(1))?;
+
+    // Raise the threshold above the current task balance
+    apps.arch_app.update_config(
+        None,
+        None,
+        None,
+        None,
+        Some(balance + Uint128::one()),
+        None,
+        None,
+        Some(Uint128::new(8_000_000)),
+    )?;
+    let manager = apps.arch_app.call_as(&cron_cat_addrs.manager);
+    manager.renew_domain(TaskId(1))?;
+    assert_eq!(
+        task_balance(&apps, TaskId(1))?,
+        balance + Uint128::new(8_000_000)
+    );
+
+    // Balances at or above the threshold are left alone
+    manager.renew_domain(TaskId(1))?;
+    assert_eq!(
+        task_balance(&apps, TaskId(1))?,
+        balance + Uint128::new(8_000_000)
+    );
+    Ok(())
+}
+
+#[test]
+fn subdomains_are_registered_and_removed() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+    let resolver = mock.addr_make("resolver");
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+
+    // Subdomains can't outlive their domain, even with expirations past the largest timestamp
+    for expiration in [domain.expiry.seconds() + 1, u64::MAX] {
+        let err = apps
+            .arch_app
+            .register_subdomain(
+                "archid".to_owned(),
+                expiration,
+                proxy.to_string(),
+                resolver.to_string(),
+                "dev".to_owned(),
+            )
+            .unwrap_err();
+        assert!(err.root().to_string().contains(
+            &AppError::SubdomainOutlivesDomain {
+                subdomain: "dev.archid.arch".to_owned(),
+                expiration,
+                domain_expiry: domain.expiry.seconds(),
+            }
+            .to_string()
+        ));
+    }
+
+    apps.arch_app.register_subdomain(
+        "archid".to_owned(),
+        domain.expiry.seconds(),
+        proxy.to_string(),
+        resolver.to_string(),
+        "dev".to_owned(),
+    )?;
+    let DomainResponse { subdomains, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(
+        subdomains,
+        vec![SubdomainRecord {
+            name: "dev.archid.arch".to_owned(),
+            owner: proxy.clone(),
+            resolver: resolver.clone(),
+            expiry: domain.expiry,
+        }]
+    );
+    assert_eq!(nft_owner(&mock, &apps.archid_token, "dev.archid.arch")?, proxy);
+    let resolution = apps.arch_app.name_resolution("dev.archid.arch".to_owned())?;
+    assert_eq!(resolution.query_resp.address, Some(resolver.to_string()));
+    let resolution = apps.arch_app.name_resolution("dev.archid".to_owned())?;
+    assert_eq!(resolution.query_resp.address, Some(resolver.to_string()));
+
+    apps.arch_app
+        .remove_subdomain("archid".to_owned(), "dev".to_owned())?;
+    let DomainResponse { subdomains, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert!(subdomains.is_empty());
+    Ok(())
+}
+
+#[test]
+fn resolver_and_metadata_are_updated_through_the_registry() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let resolver = mock.addr_make("resolver");
+
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+
+    apps.arch_app
+        .update_resolver("archid".to_owned(), Some(resolver.to_string()))?;
+    let resolution = apps.arch_app.name_resolution("archid.arch".to_owned())?;
+    assert_eq!(resolution.query_resp.address, Some(resolver.to_string()));
+    // Names resolve with or without the suffix, like they register
+    let resolution = apps.arch_app.name_resolution("archid".to_owned())?;
+    assert_eq!(resolution.query_resp.address, Some(resolver.to_string()));
+
+    let err = apps
+        .arch_app
+        .name_resolution("ArchID".to_owned())
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::InvalidDomainName {
+            name: "ArchID".to_owned(),
+            reason: "name must be lowercase".to_owned(),
+        }
+        .to_string()
+    ));
+
+    // Without a resolver the domain points back at the account
+    apps.arch_app.update_resolver("archid".to_owned(), None)?;
+    let resolution = apps.arch_app.name_resolution("archid.arch".to_owned())?;
+    assert_eq!(
+        resolution.query_resp.address,
+        Some(account.proxy()?.to_string())
+    );
+
+    apps.arch_app.update_metadata(
+        MetaDataUpdateMsg {
+            description: Some("Abstract ArchID account".to_owned()),
+            image: None,
+            accounts: None,
+            websites: None,
+        },
+        "archid".to_owned(),
+    )?;
+    let info: cw721::NftInfoResponse<archid_token::Metadata> =
+        mock.app.borrow().wrap().query_wasm_smart(
+            &apps.archid_token,
+            &cw721::Cw721QueryMsg::NftInfo {
+                token_id: "archid.arch".to_owned(),
+            },
+        )?;
+    assert_eq!(
+        info.extension.description,
+        Some("Abstract ArchID account".to_owned())
+    );
+    Ok(())
+}
+
+#[test]
+fn domains_can_only_have_one_renewal_task() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let err = apps
+        .arch_app
+        .create_auto_renewal_task(
+            "archid.arch".to_owned(),
+            "0 0 12 * * *".to_owned(),
+            Duration::Time(86_400),
+            None,
+        )
+        .unwrap_err();
+    let duplicate = AppError::DuplicateRenewalTask {
+        domain: "archid.arch".to_owned(),
+        task_id: 1,
+    };
+    assert!(err.root().to_string().contains(&duplicate.to_string()));
+
+    // Moving another task onto the domain is rejected as well
+    apps.arch_app.create_auto_renewal_task(
+        "abstract".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let err = apps
+        .arch_app
+        .update_auto_renewal_task(Some("archid".to_owned()), None, None, None, TaskId(2))
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&duplicate.to_string()));
+    Ok(())
+}
+
+#[test]
+fn transferred_domains_can_be_imported_back() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+    let recipient = mock.addr_make("recipient");
+
+    // A task created before the domain entered the domain book isn't linked to its record
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    apps.arch_app.update_default_id("archid".to_owned())?;
+    let AddressesForDefaultIdResponse { addresses } =
+        apps.arch_app.addresses_for_default_id("archid".to_owned())?;
+    assert_eq!(addresses.len(), 1);
+
+    apps.arch_app
+        .transfer_domain("archid".to_owned(), recipient.to_string())?;
+    assert_eq!(
+        nft_owner(&mock, &apps.archid_token, "archid.arch")?,
+        recipient
+    );
+    let TasksResponse { tasks } = apps.arch_app.tasks(None, None)?;
+    assert!(tasks.is_empty());
+    let AddressesForDefaultIdResponse { addresses } =
+        apps.arch_app.addresses_for_default_id("archid".to_owned())?;
+    assert!(addresses.is_empty());
+    assert!(apps.arch_app.domain("archid".to_owned()).is_err());
+
+    mock.app.borrow_mut().execute_contract(
+        recipient,
+        apps.archid_token.clone(),
+        &cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: proxy.to_string(),
+            token_id: "archid.arch".to_owned(),
+        },
+        &[],
+    )?;
+    apps.arch_app.import_domain("archid".to_owned())?;
+
+    let block = mock.block_info()?;
+    let expiry = apps
+        .arch_app
+        .name_resolution("archid.arch".to_owned())?
+        .query_resp
+        .expiration;
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(
+        domain,
+        DomainRecord {
+            name: "archid.arch".to_owned(),
+            owner: proxy,
+            token_id: "archid.arch".to_owned(),
+            registered_height: block.height,
+            registered_time: block.time,
+            expiry: Timestamp::from_seconds(expiry),
+            renewal_task: None,
+        }
+    );
+
+    // Importing a tracked domain again keeps its registration data and renewal task
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(domain.renewal_task, Some(TaskId(2)));
+    mock.wait_seconds(86_400)?;
+    apps.arch_app.import_domain("archid".to_owned())?;
+    let DomainResponse { domain: reimported, .. } = apps.arch_app.domain("archid".to_owned())?;
+    assert_eq!(reimported, domain);
+    Ok(())
+}
+
+#[test]
+fn missing_task_and_domain_are_typed_errors() -> anyhow::Result<()> {
+    let (_mock, _account, _abstr, apps, _manager_addr) = setup()?;
+
+    let err = apps.arch_app.task(TaskId(42)).unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::TaskNotFound { task_id: 42 }.to_string()));
+
+    let err = apps.arch_app.domain("archid".to_owned()).unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::DomainNotFound {
+            domain: "archid.arch".to_owned()
+        }
+        .to_string()
+    ));
+
+    let err = apps.arch_app.cancel_auto_renewal_task(TaskId(42)).unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&AppError::TaskNotFound { task_id: 42 }.to_string()));
+    Ok(())
+}
+
+#[test]
+fn handlers_emit_archid_auto_events() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, cron_cat_addrs) = setup()?;
+    let proxy = account.proxy()?.to_string();
+    let operator = mock.addr_make("operator");
+    let recipient = mock.addr_make("recipient");
+    let registry_funds = format!("{REGISTRY_BASE_COST}{REGISTRY_DENOM}");
+    let expiry = |domain: &str| -> anyhow::Result<String> {
+        let resolution = apps.arch_app.name_resolution(domain.to_owned())?;
+        Ok(resolution.query_resp.expiration.to_string())
+    };
+
+    let response = apps
+        .arch_app
+        .update_config(Some(400_000), None, None, None, None, None, None, None)?;
+    assert_eq!(
+        archid_auto_event(&response, "update_config"),
+        expected_event("update_config", &[])
+    );
+
+    let response = apps
+        .arch_app
+        .grant_role(operator.to_string(), Role::Renew)?;
+    assert_eq!(
+        archid_auto_event(&response, "grant_role"),
+        expected_event("grant_role", &[])
+    );
+    let response = apps
+        .arch_app
+        .revoke_role(operator.to_string(), Role::Renew)?;
+    assert_eq!(
+        archid_auto_event(&response, "revoke_role"),
+        expected_event("revoke_role", &[])
+    );
+
+    let response = apps.arch_app.register_domain("archid".to_owned(), None, 1)?;
+    assert_eq!(
+        archid_auto_event(&response, "register_domain"),
+        expected_event(
+            "register_domain",
+            &[
+                ("domain", "archid.arch"),
+                ("years", "1"),
+                ("funds", &registry_funds),
+                ("owner", &proxy),
+            ]
+        )
+    );
+    assert_eq!(
+        archid_auto_event(&response, "refresh_expiry"),
+        expected_event(
+            "refresh_expiry",
+            &[("domain", "archid.arch"), ("expiry", &expiry("archid.arch")?)]
+        )
+    );
+
+    let response = apps.arch_app.update_default_id("archid".to_owned())?;
+    assert_eq!(
+        archid_auto_event(&response, "update_default_id"),
+        expected_event("update_default_id", &[("domain", "archid.arch")])
+    );
+    let response = apps.arch_app.clear_default_id()?;
+    assert_eq!(
+        archid_auto_event(&response, "clear_default_id"),
+        expected_event("clear_default_id", &[])
+    );
+
+    let response = apps
+        .arch_app
+        .register_domains(vec![("abstract".to_owned(), 1)])?;
+    assert_eq!(
+        archid_auto_event(&response, "register_domains"),
+        expected_event(
+            "register_domains",
+            &[
+                ("domain", "abstract.arch"),
+                ("years", "1"),
+                ("funds", &registry_funds),
+                ("owner", &proxy),
+            ]
+        )
+    );
+    let response = apps
+        .arch_app
+        .renew_domains(vec![("abstract".to_owned(), 1)])?;
+    assert_eq!(
+        archid_auto_event(&response, "renew_domains"),
+        expected_event(
+            "renew_domains",
+            &[
+                ("domain", "abstract.arch"),
+                ("years", "1"),
+                ("funds", &registry_funds),
+            ]
+        )
+    );
+    assert_eq!(
+        archid_auto_event(&response, "refresh_expiry"),
+        expected_event(
+            "refresh_expiry",
+            &[
+                ("domain", "abstract.arch"),
+                ("expiry", &expiry("abstract.arch")?),
+            ]
+        )
+    );
+
+    let archid_expiry = expiry("archid.arch")?;
+    let response = apps.arch_app.register_subdomain(
+        "archid".to_owned(),
+        archid_expiry.parse()?,
+        proxy.clone(),
+        proxy.clone(),
+        "dev".to_owned(),
+    )?;
+    assert_eq!(
+        archid_auto_event(&response, "register_subdomain"),
+        expected_event(
+            "register_subdomain",
+            &[
+                ("domain", "dev.archid.arch"),
+                ("expiry", &archid_expiry),
+                ("owner", &proxy),
+            ]
+        )
+    );
+    let response = apps
+        .arch_app
+        .remove_subdomain("archid".to_owned(), "dev".to_owned())?;
+    assert_eq!(
+        archid_auto_event(&response, "remove_subdomain"),
+        expected_event("remove_subdomain", &[("domain", "dev.archid.arch")])
+    );
+
+    let response = apps.arch_app.update_resolver("archid".to_owned(), None)?;
+    assert_eq!(
+        archid_auto_event(&response, "update_resolver"),
+        expected_event("update_resolver", &[("domain", "archid.arch")])
+    );
+    let response = apps.arch_app.update_metadata(
+        MetaDataUpdateMsg {
+            description: Some("Abstract ArchID account".to_owned()),
+            image: None,
+            accounts: None,
+            websites: None,
+        },
+        "archid".to_owned(),
+    )?;
+    assert_eq!(
+        archid_auto_event(&response, "update_metadata"),
+        expected_event("update_metadata", &[("domain", "archid.arch")])
+    );
+
+    let task_funds = format!("5000000{DENOM}");
+    let response = apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        Some(2),
+    )?;
+    assert_eq!(
+        archid_auto_event(&response, "create_auto_renewal_task"),
+        expected_event(
+            "create_auto_renewal_task",
+            &[
+                ("domain", "archid.arch"),
+                ("years", "2"),
+                ("funds", &task_funds),
+                ("task_id", "1"),
+            ]
+        )
+    );
+    let leftover = format!("{}{DENOM}", task_balance(&apps, TaskId(1))?);
+    let response = apps.arch_app.update_auto_renewal_task(
+        None,
+        Some("0 0 12 * * *".to_owned()),
+        None,
+        None,
+        TaskId(1),
+    )?;
+    assert_eq!(
+        archid_auto_event(&response, "update_auto_renewal_task"),
+        expected_event(
+            "update_auto_renewal_task",
+            &[
+                ("domain", "archid.arch"),
+                ("years", "2"),
+                ("funds", &leftover),
+                ("task_id", "1"),
+            ]
+        )
+    );
+
+    // Outside the renewal window the run is skipped
+    let manager = apps.arch_app.call_as(&cron_cat_addrs.manager);
+    let response = manager.renew_domain(TaskId(1))?;
+    assert_eq!(
+        archid_auto_event(&response, "renew_domain_skipped"),
+        expected_event(
+            "renew_domain_skipped",
+            &[
+                ("domain", "archid.arch"),
+                ("task_id", "1"),
+                ("expiry", &archid_expiry),
+            ]
+        )
+    );
+
+    let response = apps.arch_app.cancel_auto_renewal_task(TaskId(1))?;
+    assert_eq!(
+        archid_auto_event(&response, "cancel_auto_renewal_task"),
+        expected_event(
+            "cancel_auto_renewal_task",
+            &[("domain", "archid.arch"), ("task_id", "1")]
+        )
+    );
+
+    // Transfers cancel the renewal tasks of the domain
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+    let response = apps
+        .arch_app
+        .transfer_domain("archid".to_owned(), recipient.to_string())?;
+    assert_eq!(
+        archid_auto_event(&response, "transfer_domain"),
+        expected_event(
+            "transfer_domain",
+            &[("domain", "archid.arch"), ("owner", recipient.as_str())]
+        )
+    );
+    assert_eq!(
+        archid_auto_event(&response, "cancel_auto_renewal_task"),
+        expected_event(
+            "cancel_auto_renewal_task",
+            &[("domain", "archid.arch"), ("task_id", "2")]
+        )
+    );
+
+    mock.app.borrow_mut().execute_contract(
+        recipient,
+        apps.archid_token.clone(),
+        &cw721::Cw721ExecuteMsg::TransferNft {
+            recipient: proxy.clone(),
+            token_id: "archid.arch".to_owned(),
+        },
+        &[],
+    )?;
+    let response = apps.arch_app.import_domain("archid".to_owned())?;
+    assert_eq!(
+        archid_auto_event(&response, "import_domain"),
+        expected_event(
+            "import_domain",
+            &[
+                ("domain", "archid.arch"),
+                ("expiry", &archid_expiry),
+                ("owner", &proxy),
+            ]
+        )
+    );
+
+    // Inside the renewal window the domain is renewed
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(2 * REGISTRY_BASE_EXPIRATION),
+        None,
+    )?;
+    let response = manager.renew_domain(TaskId(3))?;
+    assert_eq!(
+        archid_auto_event(&response, "renew_domain"),
+        expected_event(
+            "renew_domain",
+            &[
+                ("domain", "archid.arch"),
+                ("years", "1"),
+                ("funds", &registry_funds),
+                ("task_id", "3"),
+            ]
+        )
+    );
+    assert_eq!(
+        archid_auto_event(&response, "refresh_expiry"),
+        expected_event(
+            "refresh_expiry",
+            &[("domain", "archid.arch"), ("expiry", &expiry("archid.arch")?)]
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn unauthorized_callers_are_rejected() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, cron_cat_addrs) = setup()?;
+    let stranger = mock.addr_make("stranger");
+    let app_as_stranger = apps.arch_app.call_as(&stranger);
+    let unauthorized = |action: &str| {
+        AppError::Unauthorized {
+            sender: stranger.clone(),
+            action: action.to_owned(),
+        }
+        .to_string()
+    };
+
+    let err = app_as_stranger
+        .register_domain("archid".to_owned(), None, 1)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("register_domain")));
+
+    let err = app_as_stranger
+        .register_domains(vec![("archid".to_owned(), 1)])
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("register_domains")));
+
+    let err = app_as_stranger
+        .renew_domains(vec![("archid".to_owned(), 1)])
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("renew_domains")));
+
+    let err = app_as_stranger
+        .update_config(Some(1), None, None, None, None, None, None, None)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("update_config")));
+
+    let err = app_as_stranger
+        .transfer_domain("archid".to_owned(), stranger.to_string())
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("transfer_domain")));
+
+    let err = app_as_stranger
+        .create_auto_renewal_task(
+            "archid".to_owned(),
+            "0 0 * * * *".to_owned(),
+            Duration::Time(86_400),
+            None,
+        )
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&unauthorized("create_auto_renewal_task")));
+
+    apps.arch_app.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+
+    let err = app_as_stranger
+        .cancel_auto_renewal_task(TaskId(1))
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&unauthorized("cancel_auto_renewal_task")));
+
+    let err = app_as_stranger
+        .update_auto_renewal_task(None, None, None, Some(2), TaskId(1))
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&unauthorized("update_auto_renewal_task")));
+
+    let err = app_as_stranger
+        .register_subdomain(
+            "archid".to_owned(),
+            mock.block_info()?.time.seconds() + 86_400,
+            stranger.to_string(),
+            stranger.to_string(),
+            "dev".to_owned(),
+        )
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&unauthorized("register_subdomain")));
+
+    let err = app_as_stranger
+        .remove_subdomain("archid".to_owned(), "dev".to_owned())
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("remove_subdomain")));
+
+    let err = app_as_stranger
+        .update_resolver("archid".to_owned(), Some(stranger.to_string()))
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("update_resolver")));
+
+    let err = app_as_stranger
+        .update_metadata(
+            MetaDataUpdateMsg {
+                description: Some("Stolen".to_owned()),
+                image: None,
+                accounts: None,
+                websites: None,
+            },
+            "archid".to_owned(),
+        )
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("update_metadata")));
+
+    let err = app_as_stranger
+        .import_domain("archid".to_owned())
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("import_domain")));
+
+    // Only the CronCat manager may trigger renewals, not even the admin
+    let err = apps.arch_app.renew_domain(TaskId(1)).unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::NotCronCatManager {
+            sender: mock.sender(),
+            manager: cron_cat_addrs.manager,
+        }
+        .to_string()
+    ));
+
+    // Default IDs stay open to every sender
+    app_as_stranger.clear_default_id()?;
+    Ok(())
+}
+
+#[test]
+fn operators_act_within_granted_roles() -> anyhow::Result<()> {
+    let (mock, _account, _abstr, apps, _manager_addr) = setup()?;
+    let operator = mock.addr_make("operator");
+    let app_as_operator = apps.arch_app.call_as(&operator);
+
+    // Operators can't grant themselves roles
+    let err = app_as_operator
+        .grant_role(operator.to_string(), Role::ManageTasks)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::Unauthorized {
+            sender: operator.clone(),
+            action: "grant_role".to_owned(),
+        }
+        .to_string()
+    ));
+
+    apps.arch_app.grant_role(operator.to_string(), Role::ManageTasks)?;
+    apps.arch_app.grant_role(operator.to_string(), Role::Renew)?;
+    let RolesResponse { roles } = apps.arch_app.roles(operator.to_string())?;
+    assert_eq!(roles, vec![Role::ManageTasks, Role::Renew]);
+
+    app_as_operator.create_auto_renewal_task(
+        "archid".to_owned(),
+        "0 0 * * * *".to_owned(),
+        Duration::Time(86_400),
+        None,
+    )?;
+
+    // Roles are scoped, managing tasks doesn't allow registering
+    let err = app_as_operator
+        .register_domain("archid".to_owned(), None, 1)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::Unauthorized {
+            sender: operator.clone(),
+            action: "register_domain".to_owned(),
+        }
+        .to_string()
+    ));
+
+    apps.arch_app.revoke_role(operator.to_string(), Role::ManageTasks)?;
+    let RolesResponse { roles } = apps.arch_app.roles(operator.to_string())?;
+    assert_eq!(roles, vec![Role::Renew]);
+
+    let err = app_as_operator
+        .cancel_auto_renewal_task(TaskId(1))
+        .unwrap_err();
+    assert!(err.root().to_string().contains(
+        &AppError::Unauthorized {
+            sender: operator,
+            action: "cancel_auto_renewal_task".to_owned(),
+        }
+        .to_string()
+    ));
+    Ok(())
+}
+
+#[test]
+fn only_the_admin_registers_to_other_owners() -> anyhow::Result<()> {
+    let (mock, account, _abstr, apps, _manager_addr) = setup()?;
+    let proxy = account.proxy()?;
+    let operator = mock.addr_make("operator");
+    let app_as_operator = apps.arch_app.call_as(&operator);
+    apps.arch_app.grant_role(operator.to_string(), Role::Register)?;
+    let unauthorized = |action: &str| {
+        AppError::Unauthorized {
+            sender: operator.clone(),
+            action: action.to_owned(),
+        }
+        .to_string()
+    };
+
+    // Registering operators can't take domains out of the account
+    let err = app_as_operator
+        .register_domain("archid".to_owned(), Some(operator.to_string()), 1)
+        .unwrap_err();
+    assert!(err.root().to_string().contains(&unauthorized("register_domain")));
+
+    app_as_operator.register_domain("archid".to_owned(), Some(proxy.to_string()), 1)?;
+    app_as_operator.register_domain("abstract".to_owned(), None, 1)?;
+    assert_eq!(nft_owner(&mock, &apps.archid_token, "archid.arch")?, proxy);
+    assert_eq!(nft_owner(&mock, &apps.archid_token, "abstract.arch")?, proxy);
+
+    let DomainResponse { domain, .. } = apps.arch_app.domain("archid".to_owned())?;
+    let err = app_as_operator
+        .register_subdomain(
+            "archid".to_owned(),
+            domain.expiry.seconds(),
+            operator.to_string(),
+            operator.to_string(),
+            "dev".to_owned(),
+        )
+        .unwrap_err();
+    assert!(err
+        .root()
+        .to_string()
+        .contains(&unauthorized("register_subdomain")));
+
+    app_as_operator.register_subdomain(
+        "archid".to_owned(),
+        domain.expiry.seconds(),
+        proxy.to_string(),
+        operator.to_string(),
+        "dev".to_owned(),
+    )?;
+    assert_eq!(nft_owner(&mock, &apps.archid_token, "dev.archid.arch")?, proxy);
+
+    // The admin still decides where account domains go
+    apps.arch_app
+        .register_domain("wallet".to_owned(), Some(operator.to_string()), 1)?;
+    assert_eq!(nft_owner(&mock, &apps.archid_token, "wallet.arch")?, operator);
+    Ok(())
+}
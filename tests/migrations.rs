@@ -0,0 +1,165 @@
+use app::{
+    error::AppError,
+    migrations::{migrate, MigrationContext, CURRENT_STATE_VERSION},
+    state::{
+        default_ids, Config, TaskEntry, TaskId, CONFIG, DEFAULT_GAS_LIMIT,
+        DEFAULT_MAX_REGISTRATION_YEARS, DEFAULT_RENEWAL_YEARS, NEXT_ID, STATE_VERSION, TASK_LIST,
+    },
+};
+use cosmwasm_std::{
+    testing::{mock_env, MockStorage},
+    Addr, Order, StdResult, Storage, Uint128,
+};
+use cw_orch::anyhow;
+use cw_storage_plus::{Item, Map};
+use cw_utils::Duration;
+
+const REGISTRY: &str = "registry";
+const PROXY: &str = "proxy";
+const HOLDER: &str = "holder";
+
+#[cosmwasm_schema::cw_serde]
+struct ConfigV0 {
+    native_denom: String,
+    task_creation_amount: Uint128,
+    refill_threshold: Uint128,
+}
+
+#[cosmwasm_schema::cw_serde]
+struct TaskEntryV0 {
+    frequency: String,
+    domain_name: String,
+}
+
+/// State as written by the template-based app (0.0.2)
+fn legacy_storage() -> StdResult<MockStorage> {
+    let mut storage = MockStorage::new();
+    Item::new("config").save(
+        &mut storage,
+        &ConfigV0 {
+            native_denom: "aarch".to_owned(),
+            task_creation_amount: Uint128::new(5_000_000),
+            refill_threshold: Uint128::new(1_000_000),
+        },
+    )?;
+    Map::new("task_list").save(
+        &mut storage,
+        TaskId(1),
+        &TaskEntryV0 {
+            frequency: "0 0 * * * *".to_owned(),
+            domain_name: "archid.arch".to_owned(),
+        },
+    )?;
+    Map::<Addr, String>::new("default_id_map").save(
+        &mut storage,
+        Addr::unchecked(HOLDER),
+        &"archid.arch".to_owned(),
+    )?;
+    Item::<i32>::new("count").save(&mut storage, &7)?;
+    Ok(storage)
+}
+
+fn context(registry_addr: Option<Addr>) -> MigrationContext {
+    MigrationContext {
+        block_time: mock_env().block.time,
+        proxy: Addr::unchecked(PROXY),
+        registry_addr,
+        registry_denom: None,
+    }
+}
+
+#[test]
+fn migrates_legacy_state() -> anyhow::Result<()> {
+    let mut storage = legacy_storage()?;
+
+    migrate(&mut storage, 0, &context(Some(Addr::unchecked(REGISTRY))))?;
+
+    assert_eq!(STATE_VERSION.load(&storage)?, CURRENT_STATE_VERSION);
+    assert_eq!(
+        CONFIG.load(&storage)?,
+        Config {
+            native_denom: "aarch".to_owned(),
+            registry_denom: "aarch".to_owned(),
+            task_creation_amount: Uint128::new(5_000_000),
+            refill_threshold: Uint128::new(1_000_000),
+            registry_addr: Addr::unchecked(REGISTRY),
+            default_gas_limit: DEFAULT_GAS_LIMIT,
+            default_renewal_years: DEFAULT_RENEWAL_YEARS,
+            max_registration_years: DEFAULT_MAX_REGISTRATION_YEARS,
+        }
+    );
+    assert_eq!(
+        TASK_LIST.load(&storage, TaskId(1))?,
+        TaskEntry {
+            creator: Addr::unchecked(PROXY),
+            created_at: mock_env().block.time,
+            frequency: "0 0 * * * *".to_owned(),
+            domain_name: "archid.arch".to_owned(),
+            renewal_years: DEFAULT_RENEWAL_YEARS,
+            renew_before: Duration::Time(u64::MAX),
+            gas_limit: DEFAULT_GAS_LIMIT,
+            funding: Uint128::zero(),
+            last_run: None,
+            run_count: 0,
+            last_error: None,
+        }
+    );
+    assert_eq!(NEXT_ID.load(&storage)?, TaskId(0));
+    assert_eq!(storage.get(b"count"), None);
+
+    // Legacy default IDs were never checked against the domain owner
+    assert_eq!(
+        default_ids().may_load(&storage, Addr::unchecked(HOLDER))?,
+        None
+    );
+    let holders = default_ids()
+        .idx
+        .name
+        .prefix("archid.arch".to_owned())
+        .keys(&storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    assert!(holders.is_empty());
+    Ok(())
+}
+
+#[test]
+fn legacy_migration_requires_registry() -> anyhow::Result<()> {
+    let mut storage = legacy_storage()?;
+
+    let err = migrate(&mut storage, 0, &context(None)).unwrap_err();
+    assert_eq!(
+        err,
+        AppError::MissingMigrationField {
+            field: "registry".to_owned()
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn registry_denom_comes_from_migrate_msg() -> anyhow::Result<()> {
+    let mut storage = legacy_storage()?;
+
+    let ctx = MigrationContext {
+        registry_denom: Some("uarch".to_owned()),
+        ..context(Some(Addr::unchecked(REGISTRY)))
+    };
+    migrate(&mut storage, 0, &ctx)?;
+
+    let config = CONFIG.load(&storage)?;
+    assert_eq!(config.native_denom, "aarch");
+    assert_eq!(config.registry_denom, "uarch");
+    Ok(())
+}
+
+#[test]
+fn current_state_is_left_untouched() -> anyhow::Result<()> {
+    let mut storage = legacy_storage()?;
+    migrate(&mut storage, 0, &context(Some(Addr::unchecked(REGISTRY))))?;
+    let config = CONFIG.load(&storage)?;
+
+    // Steps already applied don't run again and don't need the migrate message values
+    migrate(&mut storage, CURRENT_STATE_VERSION, &context(None))?;
+    assert_eq!(CONFIG.load(&storage)?, config);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use app::{
+    error::AppError,
+    validation::{validate_domain_name, validate_subdomain_label, MAX_LABEL_LENGTH},
+};
+
+fn invalid(name: &str, reason: &str) -> AppError {
+    AppError::InvalidDomainName {
+        name: name.to_owned(),
+        reason: reason.to_owned(),
+    }
+}
+
+#[test]
+fn normalizes_arch_suffix() {
+    assert_eq!(validate_domain_name("archid").unwrap(), "archid.arch");
+    assert_eq!(validate_domain_name("archid.arch").unwrap(), "archid.arch");
+    assert_eq!(
+        validate_domain_name("my-name-42").unwrap(),
+        "my-name-42.arch"
+    );
+}
+
+#[test]
+fn rejects_invalid_names() {
+    assert_eq!(
+        validate_domain_name(".arch").unwrap_err(),
+        invalid(".arch", "name is empty")
+    );
+    assert_eq!(
+        validate_domain_name("ArchID").unwrap_err(),
+        invalid("ArchID", "name must be lowercase")
+    );
+    assert_eq!(
+        validate_domain_name("archid.com").unwrap_err(),
+        invalid("archid.com", "illegal character '.'")
+    );
+    assert_eq!(
+        validate_domain_name("arch_id").unwrap_err(),
+        invalid("arch_id", "illegal character '_'")
+    );
+    assert_eq!(
+        validate_domain_name("-archid").unwrap_err(),
+        invalid("-archid", "name can't start or end with '-'")
+    );
+
+    let long = "a".repeat(MAX_LABEL_LENGTH + 1);
+    assert_eq!(
+        validate_domain_name(&long).unwrap_err(),
+        invalid(
+            &long,
+            &format!("name is longer than {MAX_LABEL_LENGTH} characters")
+        )
+    );
+}
+
+#[test]
+fn subdomain_labels_have_no_suffix() {
+    assert!(validate_subdomain_label("docs").is_ok());
+    assert_eq!(
+        validate_subdomain_label("docs.arch").unwrap_err(),
+        invalid("docs.arch", "illegal character '.'")
+    );
+}